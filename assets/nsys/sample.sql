@@ -0,0 +1,100 @@
+-- Sample of an `nsys export --type=sqlite` database, reduced to the tables
+-- and columns read by NsysExport. Two benchmarks, `tiger` and `primitives`,
+-- ran on thread 1 with nested ranges; thread 2 emitted an unrelated range.
+-- Rebuild with: sqlite3 sample.sqlite < sample.sql
+
+CREATE TABLE StringIds (
+    id      INTEGER NOT NULL PRIMARY KEY,
+    value   TEXT NOT NULL
+);
+CREATE TABLE NVTX_EVENTS (
+    start       INTEGER NOT NULL,
+    end         INTEGER,
+    eventType   INTEGER NOT NULL,
+    rangeId     INTEGER,
+    category    INTEGER,
+    color       INTEGER,
+    text        TEXT,
+    globalTid   INTEGER,
+    endGlobalTid INTEGER,
+    textId      INTEGER REFERENCES StringIds(id),
+    domainId    INTEGER
+);
+CREATE TABLE CUPTI_ACTIVITY_KIND_RUNTIME (
+    start           INTEGER NOT NULL,
+    end             INTEGER NOT NULL,
+    eventClass      INTEGER NOT NULL,
+    globalTid       INTEGER,
+    correlationId   INTEGER,
+    nameId          INTEGER NOT NULL REFERENCES StringIds(id),
+    returnValue     INTEGER NOT NULL,
+    callchainId     INTEGER
+);
+CREATE TABLE CUPTI_ACTIVITY_KIND_KERNEL (
+    start           INTEGER NOT NULL,
+    end             INTEGER NOT NULL,
+    deviceId        INTEGER NOT NULL,
+    contextId       INTEGER NOT NULL,
+    streamId        INTEGER NOT NULL,
+    correlationId   INTEGER,
+    globalPid       INTEGER,
+    demangledName   INTEGER NOT NULL REFERENCES StringIds(id),
+    shortName       INTEGER NOT NULL REFERENCES StringIds(id),
+    mangledName     INTEGER REFERENCES StringIds(id),
+    gridX           INTEGER NOT NULL,
+    gridY           INTEGER NOT NULL,
+    gridZ           INTEGER NOT NULL,
+    blockX          INTEGER NOT NULL,
+    blockY          INTEGER NOT NULL,
+    blockZ          INTEGER NOT NULL
+);
+
+INSERT INTO StringIds (id, value) VALUES
+    (1, 'benching primitives'),
+    (2, 'upload'),
+    (3, 'cudaLaunchKernel_v7000'),
+    (4, 'fill_paths'),
+    (5, 'fill_paths(const float2 *, unsigned int, uchar4 *)'),
+    (6, 'resolve_coverage'),
+    (7, 'resolve_coverage(uchar4 *, unsigned int)'),
+    (8, 'warmup'),
+    (9, 'warmup()');
+
+-- NVTX ranges (59 push/pop, 60 start/end) and marks (34) on two threads.
+-- Older exports inline `text`, newer ones reference StringIds by `textId`.
+INSERT INTO NVTX_EVENTS
+    (start, end, eventType, text, globalTid, textId, domainId)
+VALUES
+    (900000,   NULL,     34, 'benchmark-stage',  281474976710657, NULL, 0),
+    (1000000,  9000000,  59, 'benching tiger',   281474976710657, NULL, 0),
+    (1200000,  4200000,  59, 'tessellate',       281474976710657, NULL, 0),
+    (4300000,  8800000,  59, 'render',           281474976710657, NULL, 0),
+    (4400000,  5400000,  59, NULL,               281474976710657, 2,    0),
+    (3000000,  6000000,  60, 'loader',           281474976710658, NULL, 0),
+    (10000000, 16000000, 59, NULL,               281474976710657, 1,    0),
+    (10500000, 13500000, 59, 'tessellate',       281474976710657, NULL, 0),
+    (13600000, 15900000, 59, 'render',           281474976710657, NULL, 0),
+    (16500000, NULL,     34, 'plot-stage',       281474976710657, NULL, 0);
+
+-- CUDA launches, correlated with the kernels they launched.
+INSERT INTO CUPTI_ACTIVITY_KIND_RUNTIME
+    (start, end, eventClass, globalTid, correlationId, nameId, returnValue)
+VALUES
+    (500000,   520000,   0, 281474976710657, 1, 3, 0),
+    (5500000,  5520000,  0, 281474976710657, 2, 3, 0),
+    (7000000,  7020000,  0, 281474976710657, 3, 3, 0),
+    (8900000,  8920000,  0, 281474976710657, 4, 3, 0),
+    (14000000, 14020000, 0, 281474976710657, 5, 3, 0),
+    (15000000, 15020000, 0, 281474976710657, 6, 3, 0);
+
+-- Kernel 4 was launched by `tiger` but only ran after its range ended.
+INSERT INTO CUPTI_ACTIVITY_KIND_KERNEL
+    (start, end, deviceId, contextId, streamId, correlationId, globalPid,
+     demangledName, shortName, gridX, gridY, gridZ, blockX, blockY, blockZ)
+VALUES
+    (600000,   700000,   0, 1, 7, 1, 281474976710656, 9, 8, 1, 1, 1, 32, 1, 1),
+    (5600000,  6400000,  0, 1, 7, 2, 281474976710656, 5, 4, 64, 1, 1, 256, 1, 1),
+    (7100000,  7600000,  0, 1, 7, 3, 281474976710656, 7, 6, 64, 1, 1, 256, 1, 1),
+    (9100000,  9400000,  0, 1, 7, 4, 281474976710656, 7, 6, 64, 1, 1, 256, 1, 1),
+    (14100000, 14500000, 0, 1, 7, 5, 281474976710656, 5, 4, 16, 1, 1, 256, 1, 1),
+    (15100000, 15300000, 0, 1, 7, 6, 281474976710656, 7, 6, 16, 1, 1, 256, 1, 1);
@@ -0,0 +1,25 @@
+use vgpu_bench::analysis::AccuracyCheck;
+use vgpu_bench::prelude::*;
+use vgpu_bench::rendering::{CpuRasterizer, CpuRenderer};
+use vgpu_bench::suites::rendering::RenderingSuite;
+use vgpu_bench::util::svg::PRIMITIVES_DIR;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Render references with 16 samples per pixel
+    CpuRasterizer::new()
+        .samples(4)
+        .render_dir(PRIMITIVES_DIR, "output/references")?;
+
+    // Check the accuracy of rendering without anti-aliasing
+    let aliased = CpuRenderer::new(CpuRasterizer::new().samples(1));
+    let benchmark = RenderingSuite::new(aliased)
+        .dir(PRIMITIVES_DIR)
+        .png_dir("output/rendered")
+        .benchmark("aliased_rasterization")
+        .accuracy(AccuracyCheck::new("output/rendered", "output/references"));
+
+    Driver::builder().add(benchmark).build().run()
+}
@@ -0,0 +1,17 @@
+use vgpu_bench::analysis::{Comparison, StatisticalTest, Thresholds};
+use vgpu_bench::prelude::*;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Compare a fresh run against a baseline produced by a previous run
+    let report = Comparison::new("baseline", "output")
+        .test(StatisticalTest::MannWhitneyU)
+        .thresholds(Thresholds::default().min_relative_change(0.1))
+        .run()?;
+    report.write("output")?;
+
+    // Fail (e.g. in CI) if any metric regressed
+    report.ensure_no_regressions()
+}
@@ -0,0 +1,29 @@
+use vgpu_bench::frames::FrameLoop;
+use vgpu_bench::prelude::*;
+use vgpu_bench::rendering::{CpuRenderer, Renderer};
+use vgpu_bench::util::svg::EXAMPLES_DIR;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Load the tiger once, then redraw it every frame
+    let tree = vgpu_bench::util::svg::parse(
+        std::path::Path::new(EXAMPLES_DIR).join("Ghostscript_Tiger.svg"),
+    )?;
+    let mut renderer = CpuRenderer::default();
+    renderer.init()?;
+    renderer.load(&tree)?;
+
+    let benchmark =
+        FrameLoop::new()
+            .frames(200)
+            .benchmark("tiger_frames", move |frame| {
+                frame.stage("encode", || renderer.prepare())?;
+                let image = frame.stage("submit", || renderer.render())?;
+                frame.stage("present", || drop(image));
+                Ok(())
+            });
+
+    Driver::builder().add(benchmark).build().run()
+}
@@ -0,0 +1,26 @@
+use vgpu_bench::generators::{Primitive, PrimitiveGenerator};
+use vgpu_bench::plotters::PlotOptions;
+use vgpu_bench::prelude::*;
+use vgpu_bench::suites::tessellation::TessellationSuite;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Sweep the number of bezigons from 1 to 100k, one benchmark per count
+    let mut driver = Driver::builder().plot(PlotOptions::new());
+    for count in [1, 10, 100, 1_000, 10_000, 100_000] {
+        let file = PrimitiveGenerator::new(Primitive::Bezigon)
+            .count(count)
+            .seed(42)
+            .write_to_dir("output/svg")?;
+        let benchmark = TessellationSuite::new()
+            .files([file])
+            .iterations(5)
+            .benchmark(format!("bezigons_{count}"));
+        driver = driver.add(benchmark);
+    }
+
+    // Plots include the scaling of each metric with the count
+    driver.build().run()
+}
@@ -7,23 +7,24 @@ struct TessellationMeasurement {
 }
 
 pub fn main() -> Result<()> {
-    BenchmarkFn::new(|| {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Annotated spans and marks are written to `annotations.csv`
+    Driver::from(Benchmark::from(BenchmarkFn::new(|| {
         let mut measurements = Measurements::new();
         // Annotating steps of a benchmark...
-        nvtx::mark("Step 1 - Begin");
-        thread::sleep(Duration::from_secs_f32(0.5));
-        measurements.push(TessellationMeasurement {
-            tessellation_time: 0.5,
-        });
-        nvtx::mark("Step 2 - Begin");
-        thread::sleep(Duration::from_secs_f32(0.35));
-        measurements.push(TessellationMeasurement {
-            tessellation_time: 0.35,
-        });
+        for step in 1..=2 {
+            let _span = span!("Step {step}");
+            let seconds = 0.5 / step as f32;
+            thread::sleep(Duration::from_secs_f32(seconds));
+            measurements.push(TessellationMeasurement {
+                tessellation_time: seconds,
+            });
+            mark!("Step {step} - End");
+        }
         // Benchmarking done!
         Ok(measurements)
-    })
-    .run("Benchmark Test")?;
-
-    Ok(())
+    })))
+    .run()
 }
@@ -0,0 +1,38 @@
+use vgpu_bench::cli::Harness;
+use vgpu_bench::prelude::*;
+
+#[measurement]
+struct ExampleMeasurement {
+    time_ns: u128,
+}
+
+fn sleepy(
+    name: &'static str,
+    millis: u64,
+    iterations: usize,
+) -> Benchmark<ExampleMeasurement> {
+    let func = BenchmarkFn::new(move || {
+        let mut measurements = Measurements::new();
+        for _ in 0..iterations {
+            let start = std::time::Instant::now();
+            std::thread::sleep(std::time::Duration::from_millis(millis));
+            measurements.push(ExampleMeasurement {
+                time_ns: start.elapsed().as_nanos(),
+            });
+        }
+        Ok(measurements)
+    });
+    Benchmark::new(BenchmarkMetadata::new(name), func)
+}
+
+// e.g. `cargo run --example harness -- run --filter short -m cpu --report`
+pub fn main() -> Result<()> {
+    Harness::new()
+        .benchmark("short_sleep", |args| {
+            sleepy("short_sleep", 5, args.iterations)
+        })
+        .benchmark("long_sleep", |args| {
+            sleepy("long_sleep", 20, args.iterations)
+        })
+        .main()
+}
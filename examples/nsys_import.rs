@@ -0,0 +1,24 @@
+use vgpu_bench::prelude::*;
+use vgpu_bench::profilers::NsysExport;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Read an `nsys export --type=sqlite` database, e.g. the one written by
+    // a run with `--nsys`, or the checked-in sample
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "assets/nsys/sample.sqlite".to_owned());
+    let export = NsysExport::open(&path)?;
+    println!("benchmarks profiled: {:?}", export.benchmarks()?);
+
+    // Keyed by the `benching {name}` range of every benchmark
+    let ranges = export.ranges()?;
+    let kernels = export.kernels()?;
+    ranges.write("output/nsys/ranges")?;
+    kernels.write("output/nsys/kernels")?;
+    println!("{} ranges, {} kernels", ranges.len(), kernels.len());
+
+    Ok(())
+}
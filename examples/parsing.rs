@@ -0,0 +1,32 @@
+use vgpu_bench::prelude::*;
+use vgpu_bench::suites::parsing::ParsingSuite;
+use vgpu_bench::util::alloc::CountingAllocator;
+use vgpu_bench::util::svg::EXAMPLES_DIR;
+
+// Count allocations made while parsing
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Parse the examples at two DPIs, and with text converted to paths
+    let mut text = usvg::Options::default();
+    text.fontdb.load_system_fonts();
+    let high_dpi = usvg::Options {
+        dpi: 192.0,
+        ..usvg::Options::default()
+    };
+    let benchmark = ParsingSuite::new()
+        .dir(EXAMPLES_DIR)
+        .options([
+            ("default", usvg::Options::default()),
+            ("high_dpi", high_dpi),
+            ("system_fonts", text),
+        ])
+        .iterations(10)
+        .benchmark("parsing");
+
+    Driver::builder().add(benchmark).build().run()
+}
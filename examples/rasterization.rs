@@ -0,0 +1,27 @@
+use vgpu_bench::prelude::*;
+use vgpu_bench::rendering::CpuRenderer;
+use vgpu_bench::suites::rendering::RenderingSuite;
+use vgpu_bench::util::svg::{EXAMPLES_DIR, PRIMITIVES_DIR};
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Render every bundled SVG on the CPU, keeping the images as references
+    let primitives = RenderingSuite::new(CpuRenderer::default())
+        .dir(PRIMITIVES_DIR)
+        .iterations(5)
+        .png_dir("output/png/primitives")
+        .benchmark("rasterization_primitives");
+    let examples = RenderingSuite::new(CpuRenderer::default())
+        .dir(EXAMPLES_DIR)
+        .iterations(5)
+        .png_dir("output/png/examples")
+        .benchmark("rasterization_examples");
+
+    Driver::builder()
+        .add(primitives)
+        .add(examples)
+        .build()
+        .run()
+}
@@ -0,0 +1,13 @@
+use vgpu_bench::prelude::*;
+use vgpu_bench::report::Report;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Summarise the output of a previous run as a single HTML page
+    let path = Report::read("output")?.write("output")?;
+    println!("report written to {}", path.display());
+
+    Ok(())
+}
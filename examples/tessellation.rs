@@ -0,0 +1,17 @@
+use vgpu_bench::prelude::*;
+use vgpu_bench::suites::tessellation::TessellationSuite;
+use vgpu_bench::util::svg::PRIMITIVES_DIR;
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Tessellate every primitive at three tolerances
+    let benchmark = TessellationSuite::new()
+        .dir(PRIMITIVES_DIR)
+        .tolerances([1.0, 0.1, 0.01])
+        .iterations(10)
+        .benchmark("tessellation");
+
+    Driver::builder().add(benchmark).build().run()
+}
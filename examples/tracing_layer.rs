@@ -0,0 +1,58 @@
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use vgpu_bench::annotate::AnnotationLayer;
+use vgpu_bench::prelude::*;
+use vgpu_bench::rendering::CpuRasterizer;
+use vgpu_bench::util::svg::EXAMPLES_DIR;
+
+#[measurement]
+struct RasterizationMeasurement {
+    file: String,
+    time_ns: u128,
+}
+
+/// A renderer instrumented with `tracing`.
+#[tracing::instrument(skip(rasterizer))]
+fn render(
+    rasterizer: &CpuRasterizer,
+    path: &std::path::Path,
+) -> Result<std::time::Duration> {
+    let tree = tracing::info_span!("parse")
+        .in_scope(|| vgpu_bench::util::svg::parse(path))?;
+    let scene = tracing::info_span!("tessellate")
+        .in_scope(|| rasterizer.tessellate(&tree))?;
+    let start = std::time::Instant::now();
+    let image = tracing::info_span!("rasterize")
+        .in_scope(|| rasterizer.rasterize(&scene));
+    tracing::info!(width = image.width(), "rasterized");
+    Ok(start.elapsed())
+}
+
+pub fn main() -> Result<()> {
+    // Init logging
+    vgpu_bench::util::logging::init_default();
+
+    // Spans are written to `annotations.csv` and summarised in `spans.csv`
+    tracing_subscriber::registry()
+        .with(AnnotationLayer::new().forward(true))
+        .init();
+
+    Driver::from(Benchmark::from(BenchmarkFn::new(|| {
+        let mut measurements = Measurements::new();
+        let rasterizer = CpuRasterizer::default();
+        let files = vgpu_bench::util::io::get_files_with_extension(
+            EXAMPLES_DIR,
+            false,
+            "svg",
+        );
+        for path in files {
+            let time = render(&rasterizer, &path)?;
+            measurements.push(RasterizationMeasurement {
+                file: path.display().to_string(),
+                time_ns: time.as_nanos(),
+            });
+        }
+        Ok(measurements)
+    })))
+    .run()
+}
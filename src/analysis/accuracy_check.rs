@@ -0,0 +1,183 @@
+use anyhow::anyhow;
+use log::warn;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::ImageDiff;
+use crate::models::AccuracyBundle;
+use crate::rendering::RgbaImage;
+use crate::util;
+use crate::Result;
+
+/// Compares the PNG images a benchmark renders into one directory to the
+/// reference images at the same relative paths in another, once the
+/// benchmark has run.
+#[derive(Debug, Clone)]
+pub struct AccuracyCheck {
+    actual_dir: PathBuf,
+    reference_dir: PathBuf,
+    diff: ImageDiff,
+    heatmaps: bool,
+}
+
+impl AccuracyCheck {
+    /// Generates an AccuracyCheck of the images in @param actual_dir against
+    /// @param reference_dir with:
+    /// + diff: ImageDiff::default()
+    /// + heatmaps: true
+    pub fn new<P, Q>(actual_dir: P, reference_dir: Q) -> Self
+    where
+        P: Into<PathBuf>,
+        Q: Into<PathBuf>,
+    {
+        AccuracyCheck {
+            actual_dir: actual_dir.into(),
+            reference_dir: reference_dir.into(),
+            diff: ImageDiff::default(),
+            heatmaps: true,
+        }
+    }
+
+    /// ImageDiff the images are compared with.
+    pub fn diff(mut self, diff: ImageDiff) -> Self {
+        self.diff = diff;
+        self
+    }
+
+    /// Whether to keep a diff heatmap of every image.
+    pub fn heatmaps(mut self, heatmaps: bool) -> Self {
+        self.heatmaps = heatmaps;
+        self
+    }
+
+    /// Compares every reference image, including those in subdirectories,
+    /// to the rendered image at the same relative path, adding the results
+    /// to @param bundle under that path. Images that are missing or cannot
+    /// be compared are logged and skipped, but an unreadable reference
+    /// directory is an error.
+    pub fn run(&self, bundle: &mut AccuracyBundle) -> Result<()> {
+        // Listing the images skips unreadable directories
+        std::fs::read_dir(&self.reference_dir).map_err(|e| {
+            anyhow!(
+                "{:?}: cannot read reference images: {e}",
+                self.reference_dir
+            )
+        })?;
+        let mut references = util::io::get_files_with_extension(
+            &self.reference_dir,
+            true,
+            "png",
+        );
+        references.sort();
+        for reference_path in references {
+            let relative = reference_path
+                .strip_prefix(&self.reference_dir)
+                .unwrap_or(&reference_path);
+            let name = relative.to_string_lossy().into_owned();
+            let actual_path = self.actual_dir.join(relative);
+            if let Err(e) =
+                self.check(name, &actual_path, &reference_path, bundle)
+            {
+                warn!("{actual_path:?}: skipped accuracy check: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the image at @param actual_path to the one at
+    /// @param reference_path, adding the results to @param bundle.
+    fn check(
+        &self,
+        name: String,
+        actual_path: &Path,
+        reference_path: &Path,
+        bundle: &mut AccuracyBundle,
+    ) -> Result<()> {
+        if !actual_path.is_file() {
+            return Err(anyhow!("no rendered image"));
+        }
+        let actual = RgbaImage::read_png(actual_path)?;
+        let reference = RgbaImage::read_png(reference_path)?;
+
+        let measurement = self.diff.compare(&name, &actual, &reference)?;
+        let heatmap = match self.heatmaps {
+            true => Some(self.diff.heatmap(&actual, &reference)?),
+            false => None,
+        };
+        bundle.measurements.push(measurement);
+        if let Some(heatmap) = heatmap {
+            bundle.heatmaps.insert(name, heatmap);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    #[test]
+    fn run_skips_missing_and_mismatched_images() -> Result<()> {
+        let dir = TempDir::new("accuracy");
+        let (actual_dir, reference_dir) =
+            (dir.join("actual"), dir.join("reference"));
+        let image = RgbaImage::new(4, 4);
+        for name in ["missing", "mismatched", "same"] {
+            image.write_png(reference_dir.join(name).with_extension("png"))?;
+        }
+        image.write_png(actual_dir.join("same.png"))?;
+        RgbaImage::new(2, 2).write_png(actual_dir.join("mismatched.png"))?;
+
+        let mut bundle = AccuracyBundle::default();
+        let result =
+            AccuracyCheck::new(&actual_dir, &reference_dir).run(&mut bundle);
+
+        assert!(result.is_ok());
+        assert_eq!(bundle.measurements.len(), 1);
+        assert_eq!(
+            bundle.heatmaps.keys().collect::<Vec<_>>(),
+            vec!["same.png"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn run_matches_images_by_relative_path() -> Result<()> {
+        let dir = TempDir::new("accuracy-nested");
+        let (actual_dir, reference_dir) =
+            (dir.join("actual"), dir.join("reference"));
+        let mut red = RgbaImage::new(4, 4);
+        for x in 0..4 {
+            red.set_pixel(x, 0, [255, 0, 0, 255]);
+        }
+        for path in ["a/tiger.png", "b/tiger.png"] {
+            red.write_png(reference_dir.join(path))?;
+        }
+        red.write_png(actual_dir.join("a/tiger.png"))?;
+        RgbaImage::new(4, 4).write_png(actual_dir.join("b/tiger.png"))?;
+
+        let mut bundle = AccuracyBundle::default();
+        AccuracyCheck::new(&actual_dir, &reference_dir).run(&mut bundle)?;
+
+        let results = bundle
+            .measurements
+            .into_iter()
+            .map(|m| (m.image, m.pixels_over_threshold))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![("a/tiger.png".to_owned(), 0), ("b/tiger.png".to_owned(), 4)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn run_fails_without_reference_images() {
+        let dir = TempDir::new("accuracy-missing");
+        let mut bundle = AccuracyBundle::default();
+        let result = AccuracyCheck::new(dir.join("actual"), dir.join("none"))
+            .run(&mut bundle);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,470 @@
+use anyhow::{anyhow, bail};
+use log::{trace, warn};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::analysis::statistics;
+use crate::models::{Measurements, Table};
+use crate::util;
+use crate::Result;
+
+/// Statistical test used to decide whether a metric changed between runs.
+#[derive(Debug, Clone, Copy)]
+pub enum StatisticalTest {
+    /// Rank-based test, robust to outliers and non-normal timings. Changes
+    /// are reported relative to the median.
+    MannWhitneyU,
+    /// Welch's unequal variances t-test. Changes are reported relative to
+    /// the mean.
+    WelchT,
+}
+
+impl Default for StatisticalTest {
+    /// Generates a Default State of MannWhitneyU.
+    fn default() -> Self {
+        StatisticalTest::MannWhitneyU
+    }
+}
+
+impl FromStr for StatisticalTest {
+    type Err = crate::Error;
+
+    /// Parses a test by name: `mann-whitney` or `welch`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "mann-whitney" | "mwu" => Ok(StatisticalTest::MannWhitneyU),
+            "welch" | "welch-t" => Ok(StatisticalTest::WelchT),
+            _ => Err(anyhow!(
+                "unknown statistical test '{s}', expected mann-whitney or welch"
+            )),
+        }
+    }
+}
+
+/// Classification of a metric against its baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+/// Limits a change must exceed before it is considered an improvement or a
+/// regression.
+#[derive(Debug, Clone)]
+pub struct Thresholds {
+    significance: f64,
+    min_relative_change: f64,
+    higher_is_better: HashSet<String>,
+}
+
+impl Default for Thresholds {
+    /// Generates Thresholds with:
+    /// + significance: 0.05
+    /// + min_relative_change: 0.05 (5%)
+    /// + every metric treated as lower-is-better
+    fn default() -> Self {
+        Thresholds {
+            significance: 0.05,
+            min_relative_change: 0.05,
+            higher_is_better: HashSet::new(),
+        }
+    }
+}
+
+impl Thresholds {
+    /// Maximum p-value for a change to be considered significant.
+    pub fn significance(mut self, alpha: f64) -> Self {
+        self.significance = alpha;
+        self
+    }
+
+    /// Minimum relative change (e.g. 0.05 for 5%) for a significant change to
+    /// be reported.
+    pub fn min_relative_change(mut self, change: f64) -> Self {
+        self.min_relative_change = change;
+        self
+    }
+
+    /// Mark a metric (column name) where larger values are improvements, such
+    /// as throughput or frames per second.
+    pub fn higher_is_better<S>(mut self, metric: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.higher_is_better.insert(metric.into());
+        self
+    }
+
+    fn classify(
+        &self,
+        metric: &str,
+        relative_change: f64,
+        p_value: f64,
+    ) -> Verdict {
+        if p_value >= self.significance
+            || relative_change.abs() < self.min_relative_change
+        {
+            return Verdict::Unchanged;
+        }
+        let increased = relative_change > 0.0;
+        if increased == self.higher_is_better.contains(metric) {
+            Verdict::Improved
+        } else {
+            Verdict::Regressed
+        }
+    }
+}
+
+/// Outcome of comparing one metric of one benchmark against its baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricComparison {
+    pub benchmark: String,
+    pub metric: String,
+    pub baseline_samples: usize,
+    pub current_samples: usize,
+    pub baseline_mean: f64,
+    pub current_mean: f64,
+    pub baseline_median: f64,
+    pub current_median: f64,
+    pub relative_change: f64,
+    pub p_value: f64,
+    pub verdict: Verdict,
+}
+
+/// Typed result of a Comparison, one entry per benchmark metric.
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    comparisons: Vec<MetricComparison>,
+}
+
+impl ComparisonReport {
+    pub fn comparisons(&self) -> &[MetricComparison] {
+        &self.comparisons
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricComparison> {
+        self.with_verdict(Verdict::Regressed)
+    }
+
+    pub fn improvements(&self) -> impl Iterator<Item = &MetricComparison> {
+        self.with_verdict(Verdict::Improved)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Fails with a summary of every regressed metric, for use in CI.
+    pub fn ensure_no_regressions(&self) -> Result<()> {
+        if self.has_regressions() {
+            let summary = self
+                .regressions()
+                .map(|c| {
+                    format!(
+                        "{}/{} ({:+.2}%)",
+                        c.benchmark,
+                        c.metric,
+                        c.relative_change * 100.0
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            bail!("regressions detected: {summary}");
+        }
+        Ok(())
+    }
+
+    /// Writes `comparison.csv` and `comparison.md` into @param dir.
+    pub fn write<P>(&self, dir: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        util::io::create_data_landing(dir)?;
+
+        let mut measurements = Measurements::new();
+        for comparison in &self.comparisons {
+            measurements.push(comparison.clone());
+        }
+        measurements.write(dir.join("comparison.csv"))?;
+
+        let md_path = dir.join("comparison.md");
+        trace!("writing comparison to {md_path:?}");
+        std::fs::write(md_path, self.to_markdown())?;
+
+        Ok(())
+    }
+
+    /// Renders the report as a markdown table.
+    pub fn to_markdown(&self) -> String {
+        let count = |verdict| self.with_verdict(verdict).count();
+        let mut md = String::from("# Comparison\n\n");
+        let _ = writeln!(
+            md,
+            "{} regressed, {} improved, {} unchanged\n",
+            count(Verdict::Regressed),
+            count(Verdict::Improved),
+            count(Verdict::Unchanged)
+        );
+        md.push_str("| Benchmark | Metric | Baseline | Current | Change ");
+        md.push_str("| p-value | Verdict |\n");
+        md.push_str("|---|---|---:|---:|---:|---:|---|\n");
+        for c in &self.comparisons {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {:.4} | {:.4} | {:+.2}% | {:.4} | {:?} |",
+                c.benchmark,
+                c.metric,
+                c.baseline_mean,
+                c.current_mean,
+                c.relative_change * 100.0,
+                c.p_value,
+                c.verdict
+            );
+        }
+        md
+    }
+
+    fn with_verdict(
+        &self,
+        verdict: Verdict,
+    ) -> impl Iterator<Item = &MetricComparison> {
+        self.comparisons
+            .iter()
+            .filter(move |c| c.verdict == verdict)
+    }
+}
+
+/// Compares the output directory of a fresh `Driver::run` against a baseline
+/// output directory produced by a previous run.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    baseline_dir: PathBuf,
+    current_dir: PathBuf,
+    test: StatisticalTest,
+    thresholds: Thresholds,
+}
+
+impl Comparison {
+    pub fn new<P, Q>(baseline_dir: P, current_dir: Q) -> Self
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        Comparison {
+            baseline_dir: baseline_dir.as_ref().to_owned(),
+            current_dir: current_dir.as_ref().to_owned(),
+            test: StatisticalTest::default(),
+            thresholds: Thresholds::default(),
+        }
+    }
+
+    /// Statistical test used to decide whether a metric changed.
+    pub fn test(mut self, test: StatisticalTest) -> Self {
+        self.test = test;
+        self
+    }
+
+    /// Thresholds used to classify metrics.
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Compares every numeric column of every benchmark's
+    /// `measurements.csv` present in both directories.
+    pub fn run(&self) -> Result<ComparisonReport> {
+        let mut comparisons = Vec::new();
+
+        let mut benchmarks = benchmark_dirs(&self.current_dir)?;
+        benchmarks.sort();
+        for name in benchmarks {
+            let current_path =
+                self.current_dir.join(&name).join("measurements.csv");
+            let baseline_path =
+                self.baseline_dir.join(&name).join("measurements.csv");
+            if !baseline_path.is_file() {
+                warn!("{name}: no baseline measurements, skipping");
+                continue;
+            }
+
+            let baseline = Table::read_csv(&baseline_path)?;
+            let current = Table::read_csv(&current_path)?;
+            for column in current.numeric_columns() {
+                let metric = column.name();
+                let baseline = match baseline.column(metric) {
+                    Some(column) if column.is_numeric() => column,
+                    _ => {
+                        warn!("{name}: '{metric}' missing from baseline");
+                        continue;
+                    }
+                };
+                comparisons.push(self.compare(
+                    &name,
+                    metric.to_owned(),
+                    &baseline.numeric_values(),
+                    &column.numeric_values(),
+                ));
+            }
+        }
+
+        Ok(ComparisonReport { comparisons })
+    }
+
+    fn compare(
+        &self,
+        benchmark: &str,
+        metric: String,
+        baseline: &[f64],
+        current: &[f64],
+    ) -> MetricComparison {
+        let (baseline_mean, current_mean) =
+            (statistics::mean(baseline), statistics::mean(current));
+        let (baseline_median, current_median) =
+            (statistics::median(baseline), statistics::median(current));
+        let (result, before, after) = match self.test {
+            StatisticalTest::MannWhitneyU => (
+                statistics::mann_whitney_u(baseline, current),
+                baseline_median,
+                current_median,
+            ),
+            StatisticalTest::WelchT => (
+                statistics::welch_t_test(baseline, current),
+                baseline_mean,
+                current_mean,
+            ),
+        };
+        let relative_change = relative_change(before, after);
+        let verdict =
+            self.thresholds
+                .classify(&metric, relative_change, result.p_value);
+
+        MetricComparison {
+            benchmark: benchmark.to_owned(),
+            metric,
+            baseline_samples: baseline.len(),
+            current_samples: current.len(),
+            baseline_mean,
+            current_mean,
+            baseline_median,
+            current_median,
+            relative_change,
+            p_value: result.p_value,
+            verdict,
+        }
+    }
+}
+
+fn relative_change(before: f64, after: f64) -> f64 {
+    if before == after {
+        0.0
+    } else if before == 0.0 {
+        f64::INFINITY.copysign(after)
+    } else {
+        (after - before) / before.abs()
+    }
+}
+
+/// Names of the benchmark directories within a Driver output directory.
+fn benchmark_dirs(dir: &Path) -> Result<Vec<String>> {
+    if !util::io::dir_exists(dir) {
+        bail!("{dir:?} does not exist");
+    }
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.join("measurements.csv").is_file() {
+            if let Some(name) = path.file_name() {
+                names.push(name.to_string_lossy().to_string());
+            }
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    /// Writes @param rows of `time_ns,fps,frames` as the measurements of
+    /// @param benchmark in @param dir.
+    fn write_measurements(
+        dir: &Path,
+        benchmark: &str,
+        rows: &[(f64, f64, f64)],
+    ) -> Result<()> {
+        let mut csv = String::from("time_ns,fps,frames\n");
+        for (time_ns, fps, frames) in rows {
+            let _ = writeln!(csv, "{time_ns},{fps},{frames}");
+        }
+        std::fs::create_dir_all(dir.join(benchmark))?;
+        std::fs::write(dir.join(benchmark).join("measurements.csv"), csv)?;
+        Ok(())
+    }
+
+    #[test]
+    fn run_detects_regressions_and_improvements() -> Result<()> {
+        let dir = TempDir::new("comparison");
+        let (baseline, current) = (dir.join("baseline"), dir.join("current"));
+        // Slower and with more frames per second, with as many frames
+        let before: Vec<_> = (0..10)
+            .map(|i| (100.0 + i as f64, 60.0 + i as f64, 5.0))
+            .collect();
+        let after: Vec<_> = (0..10)
+            .map(|i| (150.0 + i as f64, 90.0 + i as f64, 5.0))
+            .collect();
+        write_measurements(&baseline, "tiger", &before)?;
+        write_measurements(&current, "tiger", &after)?;
+        // Without a baseline, a benchmark is skipped
+        write_measurements(&current, "new", &after)?;
+
+        let report = Comparison::new(&baseline, &current)
+            .thresholds(Thresholds::default().higher_is_better("fps"))
+            .run()?;
+        let verdicts: Vec<(&str, &str, Verdict)> = report
+            .comparisons()
+            .iter()
+            .map(|c| (c.benchmark.as_str(), c.metric.as_str(), c.verdict))
+            .collect();
+
+        assert_eq!(
+            verdicts,
+            [
+                ("tiger", "time_ns", Verdict::Regressed),
+                ("tiger", "fps", Verdict::Improved),
+                ("tiger", "frames", Verdict::Unchanged),
+            ]
+        );
+        let time = &report.comparisons()[0];
+        // Relative to the medians, 104.5 and 154.5
+        assert_eq!(time.relative_change, 50.0 / 104.5);
+        assert!(time.p_value < 0.05);
+        assert!(report.ensure_no_regressions().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn run_ignores_changes_below_the_thresholds() -> Result<()> {
+        let dir = TempDir::new("comparison-thresholds");
+        let (baseline, current) = (dir.join("baseline"), dir.join("current"));
+        let before: Vec<_> =
+            (0..10).map(|i| (100.0 + i as f64, 60.0, 5.0)).collect();
+        let after: Vec<_> =
+            (0..10).map(|i| (102.0 + i as f64, 60.0, 5.0)).collect();
+        write_measurements(&baseline, "tiger", &before)?;
+        write_measurements(&current, "tiger", &after)?;
+
+        let report = Comparison::new(&baseline, &current)
+            .test(StatisticalTest::WelchT)
+            .run()?;
+
+        assert!(!report.has_regressions());
+        assert!(report.ensure_no_regressions().is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use anyhow::anyhow;
+use std::path::Path;
+use svgtypes::{PathParser, PathSegment, PointsParser};
+use usvg::{NodeExt, NodeKind, Paint, Tree};
+
+use crate::macros::measurement;
+use crate::models::Measurements;
+use crate::util;
+use crate::Result;
+
+/// Elements whose content is not drawn in place, like the nodes usvg keeps
+/// in its defs. Their content is counted where it is `<use>`d.
+const NOT_RENDERED: [&str; 6] =
+    ["clipPath", "defs", "marker", "mask", "pattern", "symbol"];
+
+/// Deepest chain of `<use>` references followed when counting segments.
+const MAX_USE_DEPTH: usize = 16;
+
+const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+
+/// Content features of one SVG file, to correlate with the benchmark results
+/// of the same `file`.
+#[measurement]
+pub struct SvgComplexity {
+    pub file: String,
+    /// Paths after usvg converts shapes and text to paths.
+    pub paths: usize,
+    /// Segments by type as authored in the source, before usvg converts
+    /// quadratics and arcs to cubics. Shapes count as their outline. Like
+    /// `paths`, clip paths and masks are left out, and the content of defs
+    /// and symbols counts where `<use>`d. Unlike `paths`, so are markers.
+    pub line_segments: usize,
+    pub quadratic_segments: usize,
+    pub cubic_segments: usize,
+    pub arc_segments: usize,
+    pub fills: usize,
+    pub strokes: usize,
+    /// Fills and strokes painted with a gradient.
+    pub gradients: usize,
+    /// Fills and strokes painted with a pattern.
+    pub patterns: usize,
+    /// Groups clipped by a clip path.
+    pub clip_paths: usize,
+    /// Groups masked by a mask.
+    pub masks: usize,
+    /// Area of the view box.
+    pub canvas_area: f64,
+    /// Area of the bounding box of all content, including strokes.
+    pub bbox_area: f64,
+    /// Deepest nesting of groups around a path.
+    pub max_depth: usize,
+    /// Summed bounding box areas of every path over the canvas area, an
+    /// estimate of how many times each pixel is drawn.
+    pub overdraw: f64,
+}
+
+impl SvgComplexity {
+    /// Extracts the features of the SVG file at @param path.
+    pub fn of_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let text = std::fs::read_to_string(path)?;
+        SvgComplexity::of_str(file, &text).map_err(|e| {
+            anyhow!("{path:?}: failed to analyze with error '{e}'")
+        })
+    }
+
+    /// Extracts the features of every SVG file under @param dir, in name
+    /// order.
+    pub fn of_dir<P>(dir: P) -> Result<Measurements<Self>>
+    where
+        P: AsRef<Path>,
+    {
+        let mut files = util::io::get_files_with_extension(dir, true, "svg");
+        files.sort();
+        files.iter().map(SvgComplexity::of_file).collect()
+    }
+
+    /// Extracts the features of the SVG document @param text, labelled as
+    /// @param file.
+    pub fn of_str<S>(file: S, text: &str) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let mut complexity = SvgComplexity {
+            file: file.into(),
+            paths: 0,
+            line_segments: 0,
+            quadratic_segments: 0,
+            cubic_segments: 0,
+            arc_segments: 0,
+            fills: 0,
+            strokes: 0,
+            gradients: 0,
+            patterns: 0,
+            clip_paths: 0,
+            masks: 0,
+            canvas_area: 0.0,
+            bbox_area: 0.0,
+            max_depth: 0,
+            overdraw: 0.0,
+        };
+        complexity.count_source_segments(text)?;
+
+        let tree = Tree::from_str(text, &usvg::Options::default().to_ref())?;
+        complexity.analyze_tree(&tree);
+        Ok(complexity)
+    }
+
+    fn count_source_segments(&mut self, text: &str) -> Result<()> {
+        let document = roxmltree::Document::parse(text)?;
+        self.count_element(&document, document.root_element(), 0);
+        Ok(())
+    }
+
+    /// Counts the segments of @param node and its drawn descendants, where
+    /// @param depth is the number of `<use>` references followed to reach
+    /// it.
+    fn count_element(
+        &mut self,
+        document: &roxmltree::Document,
+        node: roxmltree::Node,
+        depth: usize,
+    ) {
+        let points = |name| {
+            node.attribute(name)
+                .map(|points| PointsParser::from(points).count())
+                .unwrap_or(0)
+        };
+        match node.tag_name().name() {
+            name if NOT_RENDERED.contains(&name) => return,
+            "use" => {
+                // usvg rejects self-referencing uses, stop following them
+                let referenced = node
+                    .attribute((XLINK_NS, "href"))
+                    .or_else(|| node.attribute("href"))
+                    .and_then(|href| href.strip_prefix('#'))
+                    .and_then(|id| {
+                        document
+                            .descendants()
+                            .find(|n| n.attribute("id") == Some(id))
+                    })
+                    .filter(|_| depth < MAX_USE_DEPTH);
+                match referenced {
+                    // Symbols are only drawn through their content
+                    Some(symbol) if symbol.has_tag_name("symbol") => {
+                        for child in
+                            symbol.children().filter(|n| n.is_element())
+                        {
+                            self.count_element(document, child, depth + 1);
+                        }
+                    }
+                    Some(element) => {
+                        self.count_element(document, element, depth + 1)
+                    }
+                    None => {}
+                }
+            }
+            "path" => {
+                let data = node.attribute("d").unwrap_or_default();
+                // Path data is drawn up to its first error
+                for segment in PathParser::from(data).map_while(|s| s.ok()) {
+                    self.count_segment(&segment);
+                }
+            }
+            "line" => self.line_segments += 1,
+            "polyline" => {
+                self.line_segments += points("points").saturating_sub(1)
+            }
+            "polygon" => self.line_segments += points("points"),
+            "rect" => {
+                self.line_segments += 4;
+                if node.has_attribute("rx") || node.has_attribute("ry") {
+                    self.arc_segments += 4;
+                }
+            }
+            "circle" | "ellipse" => self.arc_segments += 4,
+            _ => {}
+        }
+        for child in node.children().filter(|n| n.is_element()) {
+            self.count_element(document, child, depth);
+        }
+    }
+
+    fn count_segment(&mut self, segment: &PathSegment) {
+        match segment {
+            PathSegment::LineTo { .. }
+            | PathSegment::HorizontalLineTo { .. }
+            | PathSegment::VerticalLineTo { .. } => self.line_segments += 1,
+            PathSegment::Quadratic { .. }
+            | PathSegment::SmoothQuadratic { .. } => {
+                self.quadratic_segments += 1
+            }
+            PathSegment::CurveTo { .. } | PathSegment::SmoothCurveTo { .. } => {
+                self.cubic_segments += 1
+            }
+            PathSegment::EllipticalArc { .. } => self.arc_segments += 1,
+            PathSegment::MoveTo { .. } | PathSegment::ClosePath { .. } => {}
+        }
+    }
+
+    fn analyze_tree(&mut self, tree: &Tree) {
+        let view_box = tree.svg_node().view_box.rect;
+        self.canvas_area = view_box.width() * view_box.height();
+        self.bbox_area = tree
+            .root()
+            .calculate_bbox()
+            .map(|bbox| bbox.width() * bbox.height())
+            .unwrap_or(0.0);
+
+        let mut drawn_area = 0.0;
+        for node in tree.root().descendants() {
+            if tree.is_in_defs(&node) {
+                continue;
+            }
+            match *node.borrow() {
+                NodeKind::Path(ref path) => {
+                    self.paths += 1;
+                    if let Some(ref fill) = path.fill {
+                        self.fills += 1;
+                        self.count_paint(tree, &fill.paint);
+                    }
+                    if let Some(ref stroke) = path.stroke {
+                        self.strokes += 1;
+                        self.count_paint(tree, &stroke.paint);
+                    }
+                    if let Some(bbox) = node.calculate_bbox() {
+                        drawn_area += bbox.width() * bbox.height();
+                    }
+                    let depth = node
+                        .ancestors()
+                        .filter(|n| matches!(*n.borrow(), NodeKind::Group(_)))
+                        .count();
+                    self.max_depth = self.max_depth.max(depth);
+                }
+                NodeKind::Group(ref group) => {
+                    self.clip_paths += group.clip_path.is_some() as usize;
+                    self.masks += group.mask.is_some() as usize;
+                }
+                _ => {}
+            }
+        }
+        if self.canvas_area > 0.0 {
+            self.overdraw = drawn_area / self.canvas_area;
+        }
+    }
+
+    fn count_paint(&mut self, tree: &Tree, paint: &Paint) {
+        if let Paint::Link(ref id) = paint {
+            match tree.defs_by_id(id).as_ref().map(|n| n.borrow()).as_deref() {
+                Some(NodeKind::LinearGradient(_))
+                | Some(NodeKind::RadialGradient(_)) => self.gradients += 1,
+                Some(NodeKind::Pattern(_)) => self.patterns += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg"
+    xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 100 100">
+  <defs>
+    <path id="hidden" d="M 0 0 L 10 0 L 10 10"/>
+    <clipPath id="clip"><path d="M 0 0 L 50 0 L 50 50 Z"/></clipPath>
+  </defs>
+  <symbol id="arc"><path d="M 0 0 A 5 5 0 0 1 10 10"/></symbol>
+  <mask id="mask"><rect width="100" height="100" fill="white"/></mask>
+  <g clip-path="url(#clip)">
+    <path d="M 10 10 L 20 10 Q 30 10 30 20 C 30 30 20 30 10 30 Z"
+        fill="red"/>
+  </g>
+  <use xlink:href="#arc" stroke="blue"/>
+  <use href="#hidden" fill="green"/>
+  <polygon points="60,60 70,60 70,70" fill="black"/>
+</svg>"##;
+
+    #[test]
+    fn segments_are_counted_where_drawn() -> Result<()> {
+        let complexity = SvgComplexity::of_str("test.svg", SVG)?;
+
+        assert_eq!(complexity.paths, 4);
+        // 1 of the path, 2 used from defs and 3 of the polygon
+        assert_eq!(complexity.line_segments, 6);
+        assert_eq!(complexity.quadratic_segments, 1);
+        assert_eq!(complexity.cubic_segments, 1);
+        assert_eq!(complexity.arc_segments, 1);
+        assert_eq!(complexity.clip_paths, 1);
+        assert_eq!(complexity.masks, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn self_referencing_uses_are_followed_to_a_limit() -> Result<()> {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg">
+  <g id="loop"><use href="#loop"/><line x2="10" y2="10" stroke="black"/></g>
+</svg>"##;
+        let empty = r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#;
+        let mut complexity = SvgComplexity::of_str("loop.svg", empty)?;
+        complexity.count_source_segments(svg)?;
+
+        assert_eq!(complexity.line_segments, MAX_USE_DEPTH + 1);
+        Ok(())
+    }
+}
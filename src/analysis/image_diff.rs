@@ -0,0 +1,334 @@
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+use crate::rendering::RgbaImage;
+use crate::Result;
+
+/// PSNR reported for identical images, which have no error.
+pub const MAX_PSNR: f64 = 100.0;
+
+/// Accuracy of one rendered image against its reference.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImageDiffMeasurement {
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+    /// Mean absolute error per channel, from 0 to 255.
+    pub mean_absolute_error: f64,
+    /// Largest absolute error of any channel, from 0 to 255.
+    pub max_absolute_error: u8,
+    /// Peak signal-to-noise ratio in dB, capped at MAX_PSNR.
+    pub psnr: f64,
+    /// Mean structural similarity of the luma, from -1 to 1.
+    pub ssim: f64,
+    /// Pixels with any channel off by more than the threshold.
+    pub pixels_over_threshold: usize,
+}
+
+/// Compares rendered images to reference images. Both are composited over
+/// white first, so differences hidden by transparency are not counted.
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    threshold: u8,
+    window: u32,
+}
+
+impl Default for ImageDiff {
+    /// Generates an ImageDiff type with:
+    /// + threshold: 8
+    /// + window: 8
+    fn default() -> Self {
+        ImageDiff {
+            threshold: 8,
+            window: 8,
+        }
+    }
+}
+
+impl ImageDiff {
+    pub fn new() -> Self {
+        ImageDiff::default()
+    }
+
+    /// Channel error a pixel may have without counting as over threshold.
+    pub fn threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Size of the square windows SSIM is computed over, which overlap by
+    /// half.
+    pub fn window(mut self, window: u32) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Measures @param actual against @param reference, labelled as
+    /// @param image.
+    pub fn compare<S>(
+        &self,
+        image: S,
+        actual: &RgbaImage,
+        reference: &RgbaImage,
+    ) -> Result<ImageDiffMeasurement>
+    where
+        S: Into<String>,
+    {
+        let (actual, reference) = flatten_pair(actual, reference)?;
+        let (width, height) = (actual.width, actual.height);
+
+        let mut absolute_sum = 0u64;
+        let mut squared_sum = 0u64;
+        let mut max_absolute_error = 0;
+        let mut pixels_over_threshold = 0;
+        for (a, r) in actual
+            .rgb
+            .chunks_exact(3)
+            .zip(reference.rgb.chunks_exact(3))
+        {
+            let mut pixel_error = 0;
+            for (&a, &r) in a.iter().zip(r) {
+                let error = a.abs_diff(r);
+                absolute_sum += error as u64;
+                squared_sum += error as u64 * error as u64;
+                pixel_error = pixel_error.max(error);
+            }
+            max_absolute_error = max_absolute_error.max(pixel_error);
+            pixels_over_threshold += (pixel_error > self.threshold) as usize;
+        }
+
+        let samples = actual.rgb.len().max(1) as f64;
+        let mse = squared_sum as f64 / samples;
+        let psnr = match mse > 0.0 {
+            true => (10.0 * (255.0 * 255.0 / mse).log10()).min(MAX_PSNR),
+            false => MAX_PSNR,
+        };
+
+        Ok(ImageDiffMeasurement {
+            image: image.into(),
+            width,
+            height,
+            mean_absolute_error: absolute_sum as f64 / samples,
+            max_absolute_error,
+            psnr,
+            ssim: self.ssim(&actual, &reference),
+            pixels_over_threshold,
+        })
+    }
+
+    /// Heatmap of the largest channel error of every pixel, from black for
+    /// none through red and yellow to white for the full range.
+    pub fn heatmap(
+        &self,
+        actual: &RgbaImage,
+        reference: &RgbaImage,
+    ) -> Result<RgbaImage> {
+        let (actual, reference) = flatten_pair(actual, reference)?;
+        let mut heatmap = RgbaImage::new(actual.width, actual.height);
+        let pixels = actual
+            .rgb
+            .chunks_exact(3)
+            .zip(reference.rgb.chunks_exact(3));
+        for (i, (a, r)) in pixels.enumerate() {
+            let error = a.iter().zip(r).map(|(a, r)| a.abs_diff(*r)).max();
+            let t = error.unwrap_or(0) as f32 / 255.0;
+            let ramp = |offset: f32| {
+                ((3.0 * t - offset).clamp(0.0, 1.0) * 255.0).round() as u8
+            };
+            let (x, y) = (i as u32 % actual.width, i as u32 / actual.width);
+            heatmap.set_pixel(x, y, [ramp(0.0), ramp(1.0), ramp(2.0), 255]);
+        }
+        Ok(heatmap)
+    }
+
+    /// Mean SSIM of the luma over windows overlapping by half, or over the
+    /// whole image if it is smaller than one window. Empty images are
+    /// identical.
+    fn ssim(&self, actual: &Flattened, reference: &Flattened) -> f64 {
+        const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+        const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+        let (width, height) = (actual.width, actual.height);
+        if width == 0 || height == 0 {
+            return 1.0;
+        }
+        let luma_a = actual.luma();
+        let luma_r = reference.luma();
+        let window_w = self.window.min(width);
+        let window_h = self.window.min(height);
+        let stride_w = (window_w / 2).max(1);
+        let stride_h = (window_h / 2).max(1);
+
+        let mut total = 0.0;
+        let mut windows = 0;
+        for y0 in (0..=height - window_h).step_by(stride_h as usize) {
+            for x0 in (0..=width - window_w).step_by(stride_w as usize) {
+                let (mut sum_a, mut sum_r) = (0.0, 0.0);
+                let (mut sum_aa, mut sum_rr, mut sum_ar) = (0.0, 0.0, 0.0);
+                for y in y0..y0 + window_h {
+                    for x in x0..x0 + window_w {
+                        let i = (y * width + x) as usize;
+                        let (a, r) = (luma_a[i], luma_r[i]);
+                        sum_a += a;
+                        sum_r += r;
+                        sum_aa += a * a;
+                        sum_rr += r * r;
+                        sum_ar += a * r;
+                    }
+                }
+                let n = (window_w * window_h) as f64;
+                let (mean_a, mean_r) = (sum_a / n, sum_r / n);
+                let var_a = sum_aa / n - mean_a * mean_a;
+                let var_r = sum_rr / n - mean_r * mean_r;
+                let covariance = sum_ar / n - mean_a * mean_r;
+                total += ((2.0 * mean_a * mean_r + C1)
+                    * (2.0 * covariance + C2))
+                    / ((mean_a * mean_a + mean_r * mean_r + C1)
+                        * (var_a + var_r + C2));
+                windows += 1;
+            }
+        }
+        match windows {
+            0 => 1.0,
+            _ => total / windows as f64,
+        }
+    }
+}
+
+/// RGB bytes of an image composited over white.
+struct Flattened {
+    width: u32,
+    height: u32,
+    rgb: Vec<u8>,
+}
+
+impl Flattened {
+    fn new(image: &RgbaImage) -> Self {
+        let rgb = image
+            .data()
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let alpha = p[3] as u32;
+                let over_white = |c: u8| {
+                    ((c as u32 * alpha + 255 * (255 - alpha)) / 255) as u8
+                };
+                [over_white(p[0]), over_white(p[1]), over_white(p[2])]
+            })
+            .collect();
+        Flattened {
+            width: image.width(),
+            height: image.height(),
+            rgb,
+        }
+    }
+
+    /// Rec. 601 luma of every pixel.
+    fn luma(&self) -> Vec<f64> {
+        self.rgb
+            .chunks_exact(3)
+            .map(|p| {
+                0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64
+            })
+            .collect()
+    }
+}
+
+fn flatten_pair(
+    actual: &RgbaImage,
+    reference: &RgbaImage,
+) -> Result<(Flattened, Flattened)> {
+    ensure!(
+        actual.width() == reference.width()
+            && actual.height() == reference.height(),
+        "image is {}x{} but its reference is {}x{}",
+        actual.width(),
+        actual.height(),
+        reference.width(),
+        reference.height()
+    );
+    Ok((Flattened::new(actual), Flattened::new(reference)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_images_are_identical() -> Result<()> {
+        let empty = RgbaImage::new(0, 0);
+        let measurement = ImageDiff::default().compare("", &empty, &empty)?;
+
+        assert_eq!(measurement.ssim, 1.0);
+        assert_eq!(measurement.psnr, MAX_PSNR);
+        Ok(())
+    }
+
+    fn filled(color: [u8; 4]) -> RgbaImage {
+        let mut image = RgbaImage::new(4, 4);
+        image.fill(color);
+        image
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn identical_images_have_no_error() -> Result<()> {
+        let image = filled([10, 200, 30, 255]);
+        let measurement = ImageDiff::default().compare("", &image, &image)?;
+
+        assert_eq!(measurement.mean_absolute_error, 0.0);
+        assert_eq!(measurement.max_absolute_error, 0);
+        assert_eq!(measurement.psnr, MAX_PSNR);
+        assert_close(measurement.ssim, 1.0);
+        assert_eq!(measurement.pixels_over_threshold, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn uniform_offset_has_known_errors() -> Result<()> {
+        let actual = filled([100, 100, 100, 255]);
+        let reference = filled([110, 110, 110, 255]);
+        let measurement =
+            ImageDiff::default().compare("", &actual, &reference)?;
+
+        // Every channel is off by 10, so the mean squared error is 100
+        assert_close(measurement.mean_absolute_error, 10.0);
+        assert_eq!(measurement.max_absolute_error, 10);
+        assert_close(
+            measurement.psnr,
+            10.0 * (255.0 * 255.0 / 100.0f64).log10(),
+        );
+        // Flat windows only differ in their means, of luma 100 and 110
+        let (c1, c2) = (2.55 * 2.55, 7.65 * 7.65);
+        let ssim = (2.0 * 100.0 * 110.0 + c1) * c2
+            / ((100.0 * 100.0 + 110.0 * 110.0 + c1) * c2);
+        assert!(
+            (measurement.ssim - ssim).abs() < 1e-6,
+            "{}",
+            measurement.ssim
+        );
+        assert_eq!(measurement.pixels_over_threshold, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn pixels_over_threshold_count_the_worst_channel() -> Result<()> {
+        let reference = filled([100, 100, 100, 255]);
+        let mut actual = reference.clone();
+        actual.set_pixel(0, 0, [120, 100, 100, 255]);
+        actual.set_pixel(1, 0, [100, 105, 100, 255]);
+        // Transparent pixels are compared over white
+        actual.set_pixel(2, 0, [0, 0, 0, 0]);
+
+        let diff = ImageDiff::default().threshold(5);
+        let measurement = diff.compare("", &actual, &reference)?;
+
+        assert_eq!(measurement.pixels_over_threshold, 2);
+        assert_eq!(measurement.max_absolute_error, 155);
+        let absolute_sum = 20.0 + 5.0 + 3.0 * 155.0;
+        assert_close(measurement.mean_absolute_error, absolute_sum / 48.0);
+        Ok(())
+    }
+}
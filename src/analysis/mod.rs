@@ -0,0 +1,25 @@
+mod accuracy_check;
+pub use accuracy_check::AccuracyCheck;
+
+mod comparison;
+pub use comparison::Comparison;
+pub use comparison::ComparisonReport;
+pub use comparison::MetricComparison;
+pub use comparison::StatisticalTest;
+pub use comparison::Thresholds;
+pub use comparison::Verdict;
+
+mod complexity;
+pub use complexity::SvgComplexity;
+
+mod image_diff;
+pub use image_diff::ImageDiff;
+pub use image_diff::ImageDiffMeasurement;
+pub use image_diff::MAX_PSNR;
+
+mod summary;
+pub use summary::BenchmarkSummary;
+pub use summary::MetricSummary;
+pub use summary::SpanSummary;
+
+pub mod statistics;
@@ -0,0 +1,398 @@
+//! Descriptive statistics and two-sample hypothesis tests used to compare
+//! measurement series.
+
+/// Result of a two-sample hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// The test statistic (U for Mann–Whitney, t for Welch).
+    pub statistic: f64,
+    /// Two-sided p-value of the null hypothesis that both samples come from
+    /// the same distribution.
+    pub p_value: f64,
+}
+
+/// Arithmetic mean, or NaN for an empty sample.
+pub fn mean(xs: &[f64]) -> f64 {
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+/// Unbiased sample variance, or NaN for fewer than two samples.
+pub fn variance(xs: &[f64]) -> f64 {
+    if xs.len() < 2 {
+        return f64::NAN;
+    }
+    let mean = mean(xs);
+    xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64
+}
+
+/// Sample standard deviation, or NaN for fewer than two samples.
+pub fn std_dev(xs: &[f64]) -> f64 {
+    variance(xs).sqrt()
+}
+
+/// Linearly interpolated percentile for `p` in `[0, 100]`, or NaN for an
+/// empty sample.
+pub fn percentile(xs: &[f64], p: f64) -> f64 {
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+    let mut sorted = xs.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+/// Median of a sample, or NaN for an empty sample.
+pub fn median(xs: &[f64]) -> f64 {
+    percentile(xs, 50.0)
+}
+
+/// Two-sided Mann–Whitney U test using the normal approximation with tie and
+/// continuity correction.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> TestResult {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return TestResult {
+            statistic: f64::NAN,
+            p_value: 1.0,
+        };
+    }
+
+    // Rank the pooled sample, averaging ranks of ties
+    let mut pooled = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect::<Vec<(f64, bool)>>();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < pooled.len() {
+        let mut j = i;
+        while j + 1 < pooled.len() && pooled[j + 1].0 == pooled[i].0 {
+            j += 1;
+        }
+        let ties = (j - i + 1) as f64;
+        let avg_rank = (i + j) as f64 / 2.0 + 1.0;
+        rank_sum_a += pooled[i..=j].iter().filter(|(_, in_a)| *in_a).count()
+            as f64
+            * avg_rank;
+        tie_term += ties.powi(3) - ties;
+        i = j + 1;
+    }
+
+    let u1 = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let u2 = n1 * n2 - u1;
+    let u = u1.min(u2);
+    let n = n1 + n2;
+    let mean_u = n1 * n2 / 2.0;
+    let var_u = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if var_u <= 0.0 {
+        // Every observation is tied, the samples are indistinguishable
+        return TestResult {
+            statistic: u,
+            p_value: 1.0,
+        };
+    }
+    let z = ((u - mean_u).abs() - 0.5).max(0.0) / var_u.sqrt();
+    let p_value = (2.0 * (1.0 - normal_cdf(z))).min(1.0);
+    TestResult {
+        statistic: u,
+        p_value,
+    }
+}
+
+/// Two-sided Welch's unequal variances t-test.
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> TestResult {
+    if a.len() < 2 || b.len() < 2 {
+        return TestResult {
+            statistic: f64::NAN,
+            p_value: 1.0,
+        };
+    }
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let (m1, m2) = (mean(a), mean(b));
+    let (se1, se2) = (variance(a) / n1, variance(b) / n2);
+    let se = se1 + se2;
+    if se == 0.0 {
+        // Both samples are constant, so they differ only if their means do
+        let p_value = if m1 == m2 { 1.0 } else { 0.0 };
+        return TestResult {
+            statistic: if m1 == m2 {
+                0.0
+            } else {
+                f64::INFINITY.copysign(m1 - m2)
+            },
+            p_value,
+        };
+    }
+    let t = (m1 - m2) / se.sqrt();
+    let df = se.powi(2) / (se1.powi(2) / (n1 - 1.0) + se2.powi(2) / (n2 - 1.0));
+    let p_value = (2.0 * (1.0 - student_t_cdf(t.abs(), df))).clamp(0.0, 1.0);
+    TestResult {
+        statistic: t,
+        p_value,
+    }
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / std::f64::consts::SQRT_2)
+}
+
+/// Cumulative distribution function of Student's t distribution with `df`
+/// degrees of freedom.
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let x = df / (df + t * t);
+    let tail = 0.5 * regularized_incomplete_beta(df / 2.0, 0.5, x);
+    if t >= 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Complementary error function with fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587
+                                    + t * (-0.82215223 + t * 0.17087277))))))));
+    let ans = t * poly.exp();
+    if x >= 0.0 {
+        ans
+    } else {
+        2.0 - ans
+    }
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    let mut y = x;
+    for c in COEFFICIENTS {
+        y += 1.0;
+        series += c / y;
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b)
+        + a * x.ln()
+        + b * (1.0 - x).ln())
+    .exp();
+    // The continued fraction converges quickly only on one side of the mean
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz).
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 200;
+    const EPSILON: f64 = 3.0e-14;
+    const TINY: f64 = 1.0e-300;
+
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        // Even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        // Odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values computed to 30 digits with mpmath; the tests follow
+    // scipy's `mannwhitneyu(method="asymptotic")` and
+    // `ttest_ind(equal_var=False)`.
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn descriptive_statistics() {
+        let xs = [4.0, 1.0, 3.0, 2.0, 5.0];
+        assert_eq!(mean(&xs), 3.0);
+        assert_eq!(variance(&xs), 2.5);
+        assert_eq!(median(&xs), 3.0);
+        assert_eq!(percentile(&xs, 25.0), 2.0);
+        assert_eq!(percentile(&[1.0, 2.0], 50.0), 1.5);
+        assert!(mean(&[]).is_nan());
+        assert!(variance(&[1.0]).is_nan());
+        assert!(percentile(&[], 50.0).is_nan());
+    }
+
+    #[test]
+    fn erfc_matches_reference() {
+        assert_close(erfc(0.0), 1.0, 1.2e-7);
+        assert_close(erfc(0.5), 0.479500122186953, 1.2e-7);
+        assert_close(erfc(-1.0), 1.842700792949715, 1.2e-7);
+        assert_close(erfc(2.0), 0.004677734981047266, 1.2e-9);
+        assert_close(normal_cdf(1.96), 0.9750021048517795, 1.2e-7);
+        assert_close(normal_cdf(-0.5), 0.3085375387259869, 1.2e-7);
+    }
+
+    #[test]
+    fn ln_gamma_matches_reference() {
+        assert_close(ln_gamma(0.5), 0.5723649429247001, 1e-10);
+        assert_close(ln_gamma(1.0), 0.0, 1e-10);
+        assert_close(ln_gamma(3.7), 1.428072326665388, 1e-10);
+        assert_close(ln_gamma(10.0), 12.80182748008147, 1e-9);
+    }
+
+    #[test]
+    fn incomplete_beta_matches_reference() {
+        assert_close(regularized_incomplete_beta(2.0, 3.0, 0.3), 0.3483, 1e-10);
+        assert_close(
+            regularized_incomplete_beta(5.0, 0.5, 0.9),
+            0.3166429150200123,
+            1e-10,
+        );
+        assert_close(regularized_incomplete_beta(0.5, 0.5, 0.5), 0.5, 1e-10);
+        assert_eq!(regularized_incomplete_beta(2.0, 3.0, 0.0), 0.0);
+        assert_eq!(regularized_incomplete_beta(2.0, 3.0, 1.0), 1.0);
+        assert_close(student_t_cdf(2.0, 5.0), 0.9490302605850708, 1e-10);
+        assert_close(student_t_cdf(-1.5, 12.5), 0.07922129349482493, 1e-10);
+    }
+
+    #[test]
+    fn mann_whitney_u_separated_samples() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [6.0, 7.0, 8.0, 9.0, 10.0];
+        let result = mann_whitney_u(&a, &b);
+        assert_eq!(result.statistic, 0.0);
+        assert_close(result.p_value, 0.01218578035534481, 1e-7);
+        // The test is symmetric in its samples
+        assert_eq!(mann_whitney_u(&b, &a), result);
+    }
+
+    #[test]
+    fn mann_whitney_u_overlapping_samples() {
+        let a = [1.1, 2.3, 3.5, 4.2, 5.9, 6.1];
+        let b = [2.0, 4.5, 6.7, 7.2, 8.8];
+        let result = mann_whitney_u(&a, &b);
+        assert_eq!(result.statistic, 7.0);
+        assert_close(result.p_value, 0.1709035202307975, 1e-7);
+    }
+
+    #[test]
+    fn mann_whitney_u_ties() {
+        let a = [1.0, 2.0, 2.0, 3.0, 3.0];
+        let b = [2.0, 3.0, 4.0, 4.0, 5.0];
+        let result = mann_whitney_u(&a, &b);
+        assert_eq!(result.statistic, 4.0);
+        assert_close(result.p_value, 0.08567343955231689, 1e-7);
+    }
+
+    #[test]
+    fn mann_whitney_u_constant_samples() {
+        let result = mann_whitney_u(&[3.0; 4], &[3.0; 5]);
+        assert_eq!(result.statistic, 10.0);
+        assert_eq!(result.p_value, 1.0);
+        assert_eq!(mann_whitney_u(&[], &[1.0]).p_value, 1.0);
+    }
+
+    #[test]
+    fn welch_t_test_matches_reference() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [2.0, 4.0, 6.0, 8.0, 10.0];
+        let result = welch_t_test(&a, &b);
+        assert_close(result.statistic, -1.897366596101028, 1e-12);
+        assert_close(result.p_value, 0.1075311949306272, 1e-9);
+
+        let a = [10.1, 9.8, 10.3, 10.0, 9.9, 10.2];
+        let b = [10.6, 10.9, 10.4, 11.0, 10.7];
+        let result = welch_t_test(&a, &b);
+        assert_close(result.statistic, -5.103758996996204, 1e-9);
+        assert_close(result.p_value, 0.001102705984405926, 1e-9);
+    }
+
+    #[test]
+    fn welch_t_test_constant_samples() {
+        let same = welch_t_test(&[3.0; 3], &[3.0; 3]);
+        assert_eq!((same.statistic, same.p_value), (0.0, 1.0));
+        let different = welch_t_test(&[3.0; 3], &[4.0; 3]);
+        assert_eq!(different.statistic, f64::NEG_INFINITY);
+        assert_eq!(different.p_value, 0.0);
+        let different = welch_t_test(&[4.0; 3], &[3.0; 3]);
+        assert_eq!(different.statistic, f64::INFINITY);
+        assert_eq!(different.p_value, 0.0);
+        assert_eq!(welch_t_test(&[1.0], &[1.0, 2.0]).p_value, 1.0);
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::analysis::statistics;
+use crate::annotate::Annotation;
+use crate::models::{Measurements, Table};
+
+/// Outcome of a single benchmark within a Driver run.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BenchmarkSummary {
+    pub benchmark: String,
+    pub completed: bool,
+    /// Time from the start of the benchmark, which monitor timestamps are
+    /// relative to, until its measurements were returned.
+    pub duration_ns: u64,
+    pub error: Option<String>,
+}
+
+/// Descriptive statistics of one numeric field of a benchmark's measurements.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MetricSummary {
+    pub benchmark: String,
+    pub metric: String,
+    pub count: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl MetricSummary {
+    /// Summarises every numeric column of @param table, the measurements of
+    /// @param benchmark.
+    pub fn of_table(benchmark: &str, table: &Table) -> Vec<Self> {
+        table
+            .numeric_columns()
+            .map(|column| {
+                let values = column.numeric_values();
+                MetricSummary {
+                    benchmark: benchmark.to_owned(),
+                    metric: column.name().to_owned(),
+                    count: values.len(),
+                    mean: statistics::mean(&values),
+                    std_dev: statistics::std_dev(&values),
+                    min: statistics::percentile(&values, 0.0),
+                    median: statistics::median(&values),
+                    p95: statistics::percentile(&values, 95.0),
+                    max: statistics::percentile(&values, 100.0),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Durations of all spans of one name in a benchmark's annotations.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpanSummary {
+    pub span: String,
+    pub count: usize,
+    pub total_ns: u64,
+    pub mean_ns: f64,
+    pub min_ns: f64,
+    pub median_ns: f64,
+    pub max_ns: f64,
+}
+
+impl SpanSummary {
+    /// Summarises the spans of @param annotations by name, in name order.
+    pub fn of_annotations(annotations: &Measurements<Annotation>) -> Vec<Self> {
+        let mut durations: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        for annotation in annotations.iter().filter(|a| a.kind == "span") {
+            durations
+                .entry(&annotation.name)
+                .or_default()
+                .push(annotation.duration_ns as f64);
+        }
+        durations
+            .into_iter()
+            .map(|(span, values)| SpanSummary {
+                span: span.to_owned(),
+                count: values.len(),
+                total_ns: values.iter().sum::<f64>() as u64,
+                mean_ns: statistics::mean(&values),
+                min_ns: statistics::percentile(&values, 0.0),
+                median_ns: statistics::median(&values),
+                max_ns: statistics::percentile(&values, 100.0),
+            })
+            .collect()
+    }
+}
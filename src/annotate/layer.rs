@@ -0,0 +1,166 @@
+use std::fmt::Debug;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::Id;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::annotate;
+use crate::tracers;
+
+/// A `tracing_subscriber` Layer recording every entry of a span and every
+/// event into the annotations of the running benchmark, named by the span
+/// name and the event message.
+#[derive(Debug, Clone)]
+pub struct AnnotationLayer {
+    forward: bool,
+}
+
+impl Default for AnnotationLayer {
+    /// Generates an AnnotationLayer type with:
+    /// + forward: false
+    fn default() -> Self {
+        AnnotationLayer { forward: false }
+    }
+}
+
+impl AnnotationLayer {
+    pub fn new() -> Self {
+        AnnotationLayer::default()
+    }
+
+    /// Whether to forward spans and events to the active tracer as ranges
+    /// and marks, which are NVTX ranges by default.
+    pub fn forward(mut self, forward: bool) -> Self {
+        self.forward = forward;
+        self
+    }
+}
+
+/// Start of the current entry of a span.
+struct Entered(Instant);
+
+impl<S> Layer<S> for AnnotationLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if self.forward {
+                tracers::active().range_push(span.name());
+            }
+            span.extensions_mut().replace(Entered(Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let end = Instant::now();
+        if let Some(span) = ctx.span(id) {
+            if self.forward {
+                tracers::active().range_pop();
+            }
+            if let Some(Entered(start)) = span.extensions_mut().remove() {
+                annotate::record(span.name(), "span", start, end);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor(None);
+        event.record(&mut visitor);
+        let name = visitor
+            .0
+            .unwrap_or_else(|| event.metadata().name().to_owned());
+        if self.forward {
+            tracers::active().mark(&name);
+        }
+        let now = Instant::now();
+        annotate::record(&name, "event", now, now);
+    }
+}
+
+/// Captures the `message` field of an event.
+struct MessageVisitor(Option<String>);
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0 = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::Registry;
+
+    use super::*;
+    use crate::analysis::SpanSummary;
+    use crate::models::{
+        Benchmark, BenchmarkFn, BenchmarkMetadata, DriverOptions,
+    };
+    use crate::util::testing::{self, TempDir};
+    use crate::Result;
+
+    #[derive(Serialize, Debug)]
+    struct Sample {
+        value: u32,
+    }
+
+    #[test]
+    fn layer_records_spans_and_events() -> Result<()> {
+        let _lock = testing::lock_annotations();
+        let dir = TempDir::new("layer");
+        let mut options = DriverOptions::default();
+        options.output_dir = dir.join("output");
+        let mut benchmark = Benchmark::new(
+            BenchmarkMetadata::new("layer"),
+            BenchmarkFn::new(|| {
+                for frame in 0..2 {
+                    tracing::info_span!("draw")
+                        .in_scope(|| tracing::info!(frame, "drawn"));
+                }
+                tracing::info!(frames = 2);
+                Ok([Sample { value: 1 }].into_iter().collect())
+            }),
+        );
+        let subscriber = Registry::default().with(AnnotationLayer::new());
+        let bundle = tracing::subscriber::with_default(subscriber, || {
+            benchmark.run(&options)
+        })?;
+
+        let names = bundle
+            .annotations
+            .iter()
+            .map(|a| (a.name.as_str(), a.kind.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(names.len(), 5, "{names:?}");
+        assert_eq!(
+            names[..4],
+            [
+                ("drawn", "event"),
+                ("draw", "span"),
+                ("drawn", "event"),
+                ("draw", "span")
+            ]
+        );
+        // Events without a message are named by their metadata
+        let (name, kind) = names[4];
+        assert!(name.starts_with("event "), "{name}");
+        assert_eq!(kind, "event");
+
+        let spans = SpanSummary::of_annotations(&bundle.annotations);
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].span.as_str(), spans[0].count), ("draw", 2));
+        Ok(())
+    }
+}
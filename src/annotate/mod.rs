@@ -0,0 +1,186 @@
+//! Annotations of user code inside benchmarks: spans and marks forwarded to
+//! the active tracer and collected into the `annotations` of the running
+//! benchmark. With the `tracing` feature, AnnotationLayer collects the spans
+//! and events of code instrumented with `tracing` as well.
+//!
+//! ```ignore
+//! let _span = span!("tessellate {file}");
+//! mark!("uploaded");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::models::Measurements;
+use crate::tracers;
+
+#[cfg(feature = "tracing")]
+mod layer;
+#[cfg(feature = "tracing")]
+pub use layer::AnnotationLayer;
+
+/// One span or mark recorded while a benchmark ran.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Annotation {
+    pub name: String,
+    /// `span`, `mark` or, from AnnotationLayer, `event`.
+    pub kind: String,
+    /// Time from the start of the benchmark.
+    pub start_ns: u64,
+    /// Zero for marks.
+    pub duration_ns: u64,
+}
+
+/// Start of the collection and the annotations recorded since.
+static COLLECTOR: Mutex<Option<(Instant, Vec<Annotation>)>> = Mutex::new(None);
+
+/// Starts collecting annotations, discarding any not yet taken.
+pub(crate) fn collect() {
+    *COLLECTOR.lock().unwrap() = Some((Instant::now(), Vec::new()));
+}
+
+/// Stops collecting annotations, returning those recorded since `collect`.
+pub(crate) fn take() -> Measurements<Annotation> {
+    match COLLECTOR.lock().unwrap().take() {
+        Some((_, annotations)) => annotations.into_iter().collect(),
+        None => Measurements::new(),
+    }
+}
+
+pub(crate) fn record(name: &str, kind: &str, start: Instant, end: Instant) {
+    if let Some((origin, annotations)) = COLLECTOR.lock().unwrap().as_mut() {
+        annotations.push(Annotation {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            start_ns: start.saturating_duration_since(*origin).as_nanos()
+                as u64,
+            duration_ns: (end - start).as_nanos() as u64,
+        });
+    }
+}
+
+/// Marks an instant named @param name.
+pub fn mark<S>(name: S)
+where
+    S: AsRef<str>,
+{
+    let name = name.as_ref();
+    tracers::active().mark(name);
+    let now = Instant::now();
+    record(name, "mark", now, now);
+}
+
+/// Guard of a range of the active tracer, recording its duration once
+/// dropped. Spans end on the thread they started on, innermost first.
+#[must_use = "a span ends as soon as it is dropped"]
+pub struct Span {
+    name: String,
+    start: Instant,
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl Span {
+    /// Starts a span named @param name, ending when dropped.
+    pub fn enter<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        tracers::active().range_push(&name);
+        Span {
+            name,
+            start: Instant::now(),
+            _thread_bound: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let end = Instant::now();
+        tracers::active().range_pop();
+        record(&self.name, "span", self.start, end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::models::{
+        Benchmark, BenchmarkBundle, BenchmarkFn, BenchmarkMetadata,
+        DriverOptions,
+    };
+    use crate::util::testing::{self, TempDir};
+    use crate::{mark, span, Result};
+
+    #[derive(Serialize, Debug)]
+    struct Sample {
+        value: u32,
+    }
+
+    fn run<F>(name: &str, func: F) -> Result<BenchmarkBundle<Sample>>
+    where
+        F: FnOnce() + 'static,
+    {
+        let dir = TempDir::new(name);
+        let mut options = DriverOptions::default();
+        options.output_dir = dir.join("output");
+        let metadata = BenchmarkMetadata::new(name);
+        let mut benchmark = Benchmark::new(
+            metadata,
+            BenchmarkFn::new(move || {
+                func();
+                Ok([Sample { value: 1 }].into_iter().collect())
+            }),
+        );
+        benchmark.run(&options)
+    }
+
+    #[test]
+    fn benchmark_collects_nested_spans_and_marks() -> Result<()> {
+        let _lock = testing::lock_annotations();
+        let bundle = run("annotate", || {
+            let outer = Span::enter("outer");
+            {
+                let _inner = span!("inner {}", 1);
+                mark!("mark {}", 2);
+            }
+            mark("after");
+            drop(outer);
+        })?;
+        mark("outside");
+
+        let annotations = bundle.annotations.into_iter().collect::<Vec<_>>();
+        let names = annotations
+            .iter()
+            .map(|a| (a.name.as_str(), a.kind.as_str()))
+            .collect::<Vec<_>>();
+        // Spans are recorded once they end
+        assert_eq!(
+            names,
+            [
+                ("mark 2", "mark"),
+                ("inner 1", "span"),
+                ("after", "mark"),
+                ("outer", "span")
+            ]
+        );
+        let (inner, outer) = (&annotations[1], &annotations[3]);
+        assert!(outer.start_ns <= inner.start_ns);
+        assert!(
+            inner.start_ns + inner.duration_ns
+                <= outer.start_ns + outer.duration_ns
+        );
+        assert_eq!(annotations[0].duration_ns, 0);
+        assert!(take().is_empty());
+        Ok(())
+    }
+}
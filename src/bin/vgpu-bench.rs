@@ -0,0 +1,75 @@
+use vgpu_bench::analysis::SvgComplexity;
+use vgpu_bench::cli::Harness;
+use vgpu_bench::prelude::*;
+use vgpu_bench::rendering::CpuRenderer;
+use vgpu_bench::suites::parsing::ParsingSuite;
+use vgpu_bench::suites::rendering::RenderingSuite;
+use vgpu_bench::suites::tessellation::TessellationSuite;
+use vgpu_bench::util::svg::{EXAMPLES_DIR, PRIMITIVES_DIR};
+
+// Counting every allocation slows down the other suites, so it is opt-in
+#[cfg(feature = "alloc-counting")]
+#[global_allocator]
+static ALLOCATOR: vgpu_bench::util::alloc::CountingAllocator =
+    vgpu_bench::util::alloc::CountingAllocator;
+
+pub fn main() -> Result<()> {
+    Harness::<Measurement>::new()
+        .benchmark("complexity_primitives", |_| {
+            complexity("complexity_primitives", PRIMITIVES_DIR)
+        })
+        .benchmark("complexity_examples", |_| {
+            complexity("complexity_examples", EXAMPLES_DIR)
+        })
+        .benchmark("parsing_primitives", |config| {
+            ParsingSuite::new()
+                .dir(PRIMITIVES_DIR)
+                .iterations(config.iterations)
+                .benchmark("parsing_primitives")
+                .erased()
+        })
+        .benchmark("parsing_examples", |config| {
+            ParsingSuite::new()
+                .dir(EXAMPLES_DIR)
+                .iterations(config.iterations)
+                .benchmark("parsing_examples")
+                .erased()
+        })
+        .benchmark("tessellation_primitives", |config| {
+            TessellationSuite::new()
+                .dir(PRIMITIVES_DIR)
+                .tolerances([1.0, 0.1, 0.01])
+                .iterations(config.iterations)
+                .benchmark("tessellation_primitives")
+                .erased()
+        })
+        .benchmark("tessellation_examples", |config| {
+            TessellationSuite::new()
+                .dir(EXAMPLES_DIR)
+                .tolerances([1.0, 0.1, 0.01])
+                .iterations(config.iterations)
+                .benchmark("tessellation_examples")
+                .erased()
+        })
+        .benchmark("rendering_primitives", |config| {
+            RenderingSuite::new(CpuRenderer::default())
+                .dir(PRIMITIVES_DIR)
+                .iterations(config.iterations)
+                .benchmark("rendering_primitives")
+                .erased()
+        })
+        .benchmark("rendering_examples", |config| {
+            RenderingSuite::new(CpuRenderer::default())
+                .dir(EXAMPLES_DIR)
+                .iterations(config.iterations)
+                .benchmark("rendering_examples")
+                .erased()
+        })
+        .main()
+}
+
+/// Features of every file under @param dir, to join with the suites' results.
+fn complexity(name: &str, dir: &'static str) -> Benchmark<Measurement> {
+    let func = BenchmarkFn::new(move || SvgComplexity::of_dir(dir));
+    Benchmark::new(BenchmarkMetadata::new(name), func).erased()
+}
@@ -0,0 +1,180 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::analysis::StatisticalTest;
+use crate::cli::MonitorKind;
+use crate::models::DriverWriteMode;
+use crate::plotters::PlotFormat;
+use crate::writers::OutputFormat;
+
+/// Run, inspect and compare vgpu-bench benchmark suites.
+#[derive(Parser, Debug, Clone)]
+#[clap(version, about)]
+pub struct Cli {
+    /// Increase log verbosity (-v debug, -vv trace)
+    #[clap(short, long, global = true, parse(from_occurrences))]
+    pub verbose: usize,
+
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Run the selected benchmarks
+    Run(RunArgs),
+    /// List the available benchmarks
+    List(ListArgs),
+    /// Compare an output directory against a baseline
+    Compare(CompareArgs),
+    /// Generate an HTML report of an output directory
+    Report(ReportArgs),
+}
+
+/// Knobs of a Driver run.
+#[derive(Args, Debug, Clone)]
+pub struct RunArgs {
+    /// Read the run from a TOML or YAML file, ignoring the other options
+    #[clap(long)]
+    pub config: Option<PathBuf>,
+
+    /// Directory results are written to
+    #[clap(short, long, default_value = "output")]
+    pub output_dir: PathBuf,
+
+    /// Handling of existing output: nomash, purge or relaxed
+    #[clap(short, long, default_value = "relaxed")]
+    pub write_mode: DriverWriteMode,
+
+    /// Keep running the remaining benchmarks after one fails
+    #[clap(short = 'c', long)]
+    pub on_error_continue: bool,
+
+    /// Only run benchmarks whose name contains one of these patterns
+    #[clap(short, long)]
+    pub filter: Vec<String>,
+
+    /// Iterations (samples) each benchmark measures
+    #[clap(short = 'n', long, default_value_t = 10)]
+    pub iterations: usize,
+
+    /// Monitors attached to every benchmark: cpu, heartbeat, memory
+    #[clap(short, long, value_delimiter = ',')]
+    pub monitors: Vec<MonitorKind>,
+
+    #[cfg_attr(
+        not(feature = "parquet"),
+        doc = "Output formats written: csv, json, jsonl"
+    )]
+    #[cfg_attr(
+        feature = "parquet",
+        doc = "Output formats written: csv, json, jsonl, parquet, arrow"
+    )]
+    #[clap(long, value_delimiter = ',', default_value = "csv")]
+    pub formats: Vec<OutputFormat>,
+
+    /// Plot results under `plots/` in the output directory
+    #[clap(long)]
+    pub plot: bool,
+
+    /// Image formats of plots: svg, png
+    #[clap(long, value_delimiter = ',', default_value = "svg")]
+    pub plot_formats: Vec<PlotFormat>,
+
+    /// Generate an HTML report of the run
+    #[clap(long)]
+    pub report: bool,
+
+    /// Write a Chrome trace of the run to `trace.json` in the output directory
+    #[clap(long)]
+    pub trace: bool,
+
+    /// Re-launch the run under Nsight Systems, collecting its report in the
+    /// output directory
+    #[clap(long)]
+    pub nsys: bool,
+
+    /// Path of the `nsys` executable
+    #[clap(long, default_value = "nsys")]
+    pub nsys_path: PathBuf,
+
+    /// APIs traced by Nsight Systems, e.g. cuda, nvtx, osrt, vulkan
+    #[clap(long, value_delimiter = ',', default_value = "cuda,nvtx,osrt")]
+    pub nsys_trace: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ListArgs {
+    /// Only list benchmarks whose name contains one of these patterns
+    #[clap(short, long)]
+    pub filter: Vec<String>,
+}
+
+impl ListArgs {
+    /// Whether a benchmark named @param name is selected by the filters.
+    pub fn selects(&self, name: &str) -> bool {
+        self.filter.is_empty()
+            || self.filter.iter().any(|f| name.contains(f.as_str()))
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CompareArgs {
+    /// Output directory of the baseline run
+    pub baseline: PathBuf,
+
+    /// Output directory of the run compared against the baseline
+    #[clap(default_value = "output")]
+    pub current: PathBuf,
+
+    /// Statistical test: mann-whitney or welch
+    #[clap(short, long, default_value = "mann-whitney")]
+    pub test: StatisticalTest,
+
+    /// Maximum p-value of a significant change
+    #[clap(long, default_value_t = 0.05)]
+    pub significance: f64,
+
+    /// Minimum relative change reported, e.g. 0.05 for 5%
+    #[clap(long, default_value_t = 0.05)]
+    pub min_change: f64,
+
+    /// Metrics where larger values are improvements
+    #[clap(long)]
+    pub higher_is_better: Vec<String>,
+
+    /// Exit with an error if any metric regressed
+    #[clap(long)]
+    pub fail_on_regression: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ReportArgs {
+    /// Output directory of a run
+    #[clap(default_value = "output")]
+    pub dir: PathBuf,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn formats_help_lists_the_compiled_in_formats() {
+        let command = Cli::command();
+        let run = command.find_subcommand("run").unwrap();
+        let formats = run
+            .get_arguments()
+            .find(|arg| arg.get_id() == "formats")
+            .unwrap();
+        let help = formats.get_help().unwrap();
+
+        let names = help.trim_start_matches("Output formats written: ");
+        for name in names.split(", ") {
+            assert!(name.parse::<OutputFormat>().is_ok(), "{name}");
+        }
+        assert_eq!(help.contains("parquet"), cfg!(feature = "parquet"));
+    }
+}
@@ -0,0 +1,312 @@
+use anyhow::{anyhow, bail};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::cli::{MonitorKind, RunArgs};
+use crate::models::{
+    Benchmark, DriverBuilder, DriverWriteMode, Measurable, MonitorFrequency,
+};
+use crate::plotters::{PlotFormat, PlotOptions};
+use crate::profilers::NsysProfiler;
+use crate::tracers::ChromeTracer;
+use crate::writers::OutputFormat;
+use crate::Result;
+
+/// Description of a run, from command-line arguments or a configuration file:
+///
+/// ```toml
+/// output_dir = "output"
+/// write_mode = "purge"
+/// on_error_continue = true
+/// benchmarks = ["tessellation"]
+/// iterations = 20
+/// writers = ["csv", "json"]
+/// report = true
+/// trace = true
+///
+/// [[monitors]]
+/// kind = "cpu"
+/// frequency = "500ms"
+///
+/// [plot]
+/// formats = ["svg", "png"]
+///
+/// [nsys]
+/// path = "/opt/nvidia/nsight-systems/bin/nsys"
+/// trace = ["cuda", "nvtx"]
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    /// Directory results are written to.
+    pub output_dir: PathBuf,
+    #[serde(deserialize_with = "parse")]
+    pub write_mode: DriverWriteMode,
+    pub on_error_continue: bool,
+    /// Patterns selecting the benchmarks to run by name, or all if empty.
+    pub benchmarks: Vec<String>,
+    /// Iterations (samples) each benchmark measures.
+    pub iterations: usize,
+    pub monitors: Vec<MonitorConfig>,
+    #[serde(deserialize_with = "parse_all")]
+    pub writers: Vec<OutputFormat>,
+    pub plot: Option<PlotConfig>,
+    /// Generate an HTML report of the run.
+    pub report: bool,
+    /// Write a Chrome trace of the run to `trace.json`.
+    pub trace: bool,
+    /// Re-launch the run under Nsight Systems.
+    pub nsys: Option<NsysConfig>,
+}
+
+/// A Monitor attached to every benchmark of a run.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorConfig {
+    #[serde(deserialize_with = "parse")]
+    pub kind: MonitorKind,
+    /// Polling frequency, e.g. `10hz` or `500ms`, or the kind's default.
+    #[serde(default, deserialize_with = "parse_some")]
+    pub frequency: Option<MonitorFrequency>,
+}
+
+/// Plot stage of a run.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlotConfig {
+    #[serde(deserialize_with = "parse_all")]
+    pub formats: Vec<PlotFormat>,
+    pub comparison: bool,
+}
+
+/// Nsight Systems profiling of a run.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct NsysConfig {
+    /// Path of the `nsys` executable.
+    pub path: PathBuf,
+    /// APIs traced, e.g. `cuda`, `nvtx`, `osrt` or `vulkan`.
+    pub trace: Vec<String>,
+}
+
+impl Default for RunConfig {
+    /// Generates a RunConfig type with:
+    /// + output_dir: 'output'
+    /// + write_mode: DriverWriteMode::Relaxed
+    /// + on_error_continue: false
+    /// + benchmarks: [] (all)
+    /// + iterations: 10
+    /// + monitors: []
+    /// + writers: [OutputFormat::Csv]
+    /// + plot: None
+    /// + report: false
+    /// + trace: false
+    /// + nsys: None
+    fn default() -> Self {
+        RunConfig {
+            output_dir: PathBuf::from("output"),
+            write_mode: DriverWriteMode::default(),
+            on_error_continue: false,
+            benchmarks: Vec::new(),
+            iterations: 10,
+            monitors: Vec::new(),
+            writers: vec![OutputFormat::default()],
+            plot: None,
+            report: false,
+            trace: false,
+            nsys: None,
+        }
+    }
+}
+
+impl Default for NsysConfig {
+    /// Generates a NsysConfig type with:
+    /// + path: 'nsys'
+    /// + trace: [cuda, nvtx, osrt]
+    fn default() -> Self {
+        NsysConfig {
+            path: PathBuf::from("nsys"),
+            trace: vec!["cuda".into(), "nvtx".into(), "osrt".into()],
+        }
+    }
+}
+
+impl Default for PlotConfig {
+    /// Generates a Default State of PlotOptions::default().
+    fn default() -> Self {
+        let options = PlotOptions::default();
+        PlotConfig {
+            formats: options.formats,
+            comparison: options.comparison,
+        }
+    }
+}
+
+impl From<&RunArgs> for RunConfig {
+    fn from(args: &RunArgs) -> Self {
+        let monitors = args.monitors.iter().map(|&kind| MonitorConfig {
+            kind,
+            frequency: None,
+        });
+        let plot = args.plot.then(|| PlotConfig {
+            formats: args.plot_formats.clone(),
+            ..PlotConfig::default()
+        });
+        let nsys = args.nsys.then(|| NsysConfig {
+            path: args.nsys_path.clone(),
+            trace: args.nsys_trace.clone(),
+        });
+        RunConfig {
+            output_dir: args.output_dir.clone(),
+            write_mode: args.write_mode,
+            on_error_continue: args.on_error_continue,
+            benchmarks: args.filter.clone(),
+            iterations: args.iterations,
+            monitors: monitors.collect(),
+            writers: args.formats.clone(),
+            plot,
+            report: args.report,
+            trace: args.trace,
+            nsys,
+        }
+    }
+}
+
+impl RunConfig {
+    /// Reads and validates a configuration file, in TOML or, by a `.yaml` or
+    /// `.yml` extension, YAML. Errors name the offending key.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("{}: {e}", path.display()))?;
+        let extension = path.extension().and_then(|e| e.to_str());
+        let config = match extension {
+            Some("yaml" | "yml") => RunConfig::from_yaml(&text),
+            _ => RunConfig::from_toml(&text),
+        };
+        config.map_err(|e| anyhow!("{}: {e}", path.display()))
+    }
+
+    /// Parses and validates a TOML configuration.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let mut deserializer = toml::Deserializer::new(text);
+        let config: RunConfig =
+            serde_path_to_error::deserialize(&mut deserializer)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parses and validates a YAML configuration.
+    pub fn from_yaml(text: &str) -> Result<Self> {
+        let deserializer = serde_yaml::Deserializer::from_str(text);
+        let config: RunConfig = serde_path_to_error::deserialize(deserializer)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks values which parse but cannot be run.
+    pub fn validate(&self) -> Result<()> {
+        if self.iterations == 0 {
+            bail!("iterations: must be at least 1");
+        }
+        if self.writers.is_empty() {
+            bail!("writers: at least one output format is required");
+        }
+        if let Some(plot) = &self.plot {
+            if plot.formats.is_empty() {
+                bail!("plot.formats: at least one plot format is required");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a benchmark named @param name is selected.
+    pub fn selects(&self, name: &str) -> bool {
+        self.benchmarks.is_empty()
+            || self.benchmarks.iter().any(|b| name.contains(b.as_str()))
+    }
+
+    /// Applies the output options (directory, write mode, error handling,
+    /// writers, plots, tracing and profiling) of this run to @param builder.
+    pub fn apply<T>(&self, builder: DriverBuilder<T>) -> DriverBuilder<T>
+    where
+        T: Measurable,
+    {
+        let builder = builder
+            .output_dir(&self.output_dir)
+            .write_mode(self.write_mode)
+            .on_error_contune(self.on_error_continue)
+            .writers(self.writers.iter().copied());
+        let builder = match self.trace {
+            true => builder.tracer(ChromeTracer::new()),
+            false => builder,
+        };
+        let builder = match &self.nsys {
+            Some(nsys) => builder.profiler(
+                NsysProfiler::new()
+                    .nsys(&nsys.path)
+                    .trace(nsys.trace.iter().cloned()),
+            ),
+            None => builder,
+        };
+        match &self.plot {
+            Some(plot) => builder.plot(
+                PlotOptions::new()
+                    .formats(plot.formats.iter().copied())
+                    .comparison(plot.comparison),
+            ),
+            None => builder,
+        }
+    }
+}
+
+impl MonitorConfig {
+    /// Attach this Monitor to @param benchmark.
+    pub fn attach<T>(&self, benchmark: Benchmark<T>) -> Benchmark<T>
+    where
+        T: Measurable,
+    {
+        let frequency = self
+            .frequency
+            .unwrap_or_else(|| self.kind.default_frequency());
+        self.kind.attach(benchmark, frequency)
+    }
+}
+
+fn parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(D::Error::custom)
+}
+
+fn parse_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    parse(deserializer).map(Some)
+}
+
+fn parse_all<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let values = Vec::<String>::deserialize(deserializer)?;
+    values
+        .iter()
+        .map(|value| value.parse().map_err(D::Error::custom))
+        .collect()
+}
@@ -0,0 +1,182 @@
+use anyhow::bail;
+use clap::Parser;
+use log::{info, warn, LevelFilter};
+use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
+use std::path::Path;
+
+use crate::analysis::{Comparison, Thresholds};
+use crate::cli::{
+    Cli, Command, CompareArgs, ListArgs, ReportArgs, RunArgs, RunConfig,
+};
+use crate::models::{Benchmark, DriverBuilder, Measurable};
+use crate::report::Report;
+use crate::util;
+use crate::writers::OutputFormat;
+use crate::Result;
+
+type BenchmarkFactory<T> = Box<dyn Fn(&RunConfig) -> Benchmark<T>>;
+
+/// Registry of named benchmarks driven by command-line arguments.
+pub struct Harness<T>
+where
+    T: Measurable,
+{
+    benchmarks: Vec<(String, BenchmarkFactory<T>)>,
+}
+
+impl<T> Default for Harness<T>
+where
+    T: Measurable,
+{
+    /// Generates a Default State with no benchmarks.
+    fn default() -> Self {
+        Harness {
+            benchmarks: Vec::new(),
+        }
+    }
+}
+
+impl<T> Harness<T>
+where
+    T: Measurable,
+{
+    pub fn new() -> Self {
+        Harness::default()
+    }
+
+    /// Register a benchmark selected by @param name, which @param factory
+    /// builds from the run configuration (e.g. `iterations`) only when
+    /// selected.
+    pub fn benchmark<S, F>(mut self, name: S, factory: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&RunConfig) -> Benchmark<T> + 'static,
+    {
+        self.benchmarks.push((name.into(), Box::new(factory)));
+        self
+    }
+
+    /// Names of the registered benchmarks, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.benchmarks.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// DriverBuilder of the benchmarks selected by @param config, with its
+    /// options applied and monitors attached.
+    pub fn driver(&self, config: &RunConfig) -> DriverBuilder<T> {
+        let mut builder = config.apply(DriverBuilder::new());
+        for (name, factory) in &self.benchmarks {
+            if config.selects(name) {
+                let benchmark = config
+                    .monitors
+                    .iter()
+                    .fold(factory(config), |bm, monitor| monitor.attach(bm));
+                builder = builder.add(benchmark);
+            }
+        }
+        builder
+    }
+
+    /// Reads the configuration file at @param path, checking that each of
+    /// its `benchmarks` patterns selects a registered benchmark.
+    pub fn config<P>(&self, path: P) -> Result<RunConfig>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let config = RunConfig::read(path)?;
+        for (i, pattern) in config.benchmarks.iter().enumerate() {
+            if !self.names().any(|name| name.contains(pattern.as_str())) {
+                bail!(
+                    "{}: benchmarks[{i}]: no benchmark matches '{pattern}'",
+                    path.display()
+                );
+            }
+        }
+        Ok(config)
+    }
+
+    /// Parse the process arguments, initialize logging and execute the
+    /// parsed command.
+    pub fn main(self) -> Result<()> {
+        let cli = Cli::parse();
+        let level = match cli.verbose {
+            0 => LevelFilter::Info,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        util::logging::init(vec![TermLogger::new(
+            level,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        )]);
+        self.execute(&cli)
+    }
+
+    /// Execute the command of @param cli.
+    pub fn execute(&self, cli: &Cli) -> Result<()> {
+        match &cli.command {
+            Command::Run(args) => self.run(args),
+            Command::List(args) => self.list(args),
+            Command::Compare(args) => compare(args),
+            Command::Report(args) => report(args),
+        }
+    }
+
+    fn run(&self, args: &RunArgs) -> Result<()> {
+        let config = match &args.config {
+            Some(path) => self.config(path)?,
+            None => RunConfig::from(args),
+        };
+        let driver = self.driver(&config);
+        if driver.benchmarks.is_empty() {
+            warn!("no benchmarks selected");
+            return Ok(());
+        }
+        driver.build().run()?;
+
+        if config.report {
+            if !config.writers.contains(&OutputFormat::Csv) {
+                warn!("reports are generated from csv output");
+            }
+            let path =
+                Report::read(&config.output_dir)?.write(&config.output_dir)?;
+            info!("report written to {path:?}");
+        }
+        Ok(())
+    }
+
+    fn list(&self, args: &ListArgs) -> Result<()> {
+        for name in self.names().filter(|name| args.selects(name)) {
+            println!("{name}");
+        }
+        Ok(())
+    }
+}
+
+fn compare(args: &CompareArgs) -> Result<()> {
+    let thresholds = args.higher_is_better.iter().fold(
+        Thresholds::default()
+            .significance(args.significance)
+            .min_relative_change(args.min_change),
+        |thresholds, metric| thresholds.higher_is_better(metric),
+    );
+    let report = Comparison::new(&args.baseline, &args.current)
+        .test(args.test)
+        .thresholds(thresholds)
+        .run()?;
+    report.write(&args.current)?;
+    println!("{}", report.to_markdown());
+
+    if args.fail_on_regression {
+        report.ensure_no_regressions()?;
+    }
+    Ok(())
+}
+
+fn report(args: &ReportArgs) -> Result<()> {
+    let path = Report::read(&args.dir)?.write(&args.dir)?;
+    info!("report written to {path:?}");
+    Ok(())
+}
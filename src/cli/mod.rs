@@ -0,0 +1,23 @@
+//! Command-line interface shared by the `vgpu-bench` binary and user
+//! harnesses. A harness registers its benchmarks with a [`Harness`], which
+//! parses [`Cli`] arguments and applies them to a Driver.
+
+mod args;
+pub use args::Cli;
+pub use args::Command;
+pub use args::CompareArgs;
+pub use args::ListArgs;
+pub use args::ReportArgs;
+pub use args::RunArgs;
+
+mod config;
+pub use config::MonitorConfig;
+pub use config::NsysConfig;
+pub use config::PlotConfig;
+pub use config::RunConfig;
+
+mod monitor_kind;
+pub use monitor_kind::MonitorKind;
+
+mod harness;
+pub use harness::Harness;
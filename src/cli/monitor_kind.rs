@@ -0,0 +1,69 @@
+use anyhow::anyhow;
+use std::str::FromStr;
+
+use crate::models::{Benchmark, Measurable, MonitorFrequency};
+use crate::monitors::{
+    CpuUtilizationMonitor, HeartbeatMonitor, MemoryUtilizationMonitor,
+};
+
+/// Built-in Monitor selectable from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorKind {
+    Cpu,
+    Heartbeat,
+    Memory,
+}
+
+impl FromStr for MonitorKind {
+    type Err = crate::Error;
+
+    /// Parses a monitor by name: `cpu`, `heartbeat` or `memory`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "cpu" => Ok(MonitorKind::Cpu),
+            "heartbeat" => Ok(MonitorKind::Heartbeat),
+            "memory" => Ok(MonitorKind::Memory),
+            _ => Err(anyhow!(
+                "unknown monitor '{s}', expected cpu, heartbeat or memory"
+            )),
+        }
+    }
+}
+
+impl MonitorKind {
+    /// Polling frequency used when none is configured.
+    pub fn default_frequency(&self) -> MonitorFrequency {
+        match self {
+            MonitorKind::Cpu => MonitorFrequency::Hertz(1),
+            MonitorKind::Heartbeat => MonitorFrequency::Hertz(2),
+            MonitorKind::Memory => MonitorFrequency::Hertz(10),
+        }
+    }
+
+    /// Attach a new instance of this Monitor, polling at @param frequency, to
+    /// @param benchmark.
+    pub fn attach<T>(
+        &self,
+        benchmark: Benchmark<T>,
+        frequency: MonitorFrequency,
+    ) -> Benchmark<T>
+    where
+        T: Measurable,
+    {
+        match self {
+            MonitorKind::Cpu => benchmark.monitor(CpuUtilizationMonitor {
+                name: "CPU Utilization",
+                frequency,
+            }),
+            MonitorKind::Heartbeat => {
+                benchmark.monitor(HeartbeatMonitor::with_frequency(frequency))
+            }
+            MonitorKind::Memory => {
+                benchmark.monitor(MemoryUtilizationMonitor {
+                    frequency,
+                    ..MemoryUtilizationMonitor::default()
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+/// One frame of a FrameLoop, given to its callback to time named stages,
+/// such as `upload`, `encode`, `submit` and `present`.
+#[derive(Debug)]
+pub struct Frame {
+    index: usize,
+    stages: Vec<(String, Duration)>,
+}
+
+impl Frame {
+    pub(crate) fn new(index: usize) -> Self {
+        Frame {
+            index,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Index of this frame, counting from 0 after warmup.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Runs @param func as the stage @param name of this frame, returning
+    /// its result. Stages with the same name accumulate.
+    pub fn stage<S, T, F>(&mut self, name: S, func: F) -> T
+    where
+        S: Into<String>,
+        F: FnOnce() -> T,
+    {
+        let start = Instant::now();
+        let value = func();
+        self.record(name, start.elapsed());
+        value
+    }
+
+    /// Adds @param duration to the stage @param name of this frame, for
+    /// times measured elsewhere, e.g. by GPU timer queries.
+    pub fn record<S>(&mut self, name: S, duration: Duration)
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        match self.stages.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, total)) => *total += duration,
+            None => self.stages.push((name, duration)),
+        }
+    }
+
+    pub(crate) fn finish(self, frame_time: Duration) -> FrameTiming {
+        FrameTiming {
+            frame: self.index,
+            frame_time,
+            stages: self.stages,
+        }
+    }
+}
+
+/// CPU times of one completed frame.
+#[derive(Debug, Clone)]
+pub struct FrameTiming {
+    pub frame: usize,
+    /// Time of the whole frame, including time outside of any stage.
+    pub frame_time: Duration,
+    /// Time of every stage, in the order they first ran.
+    pub stages: Vec<(String, Duration)>,
+}
+
+impl FrameTiming {
+    /// Time of the stage @param name, or zero if it did not run.
+    pub fn stage(&self, name: &str) -> Duration {
+        self.stages
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, duration)| *duration)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_with_the_same_name_accumulate() {
+        let mut frame = Frame::new(3);
+        frame.record("encode", Duration::from_millis(1));
+        frame.record("submit", Duration::from_millis(2));
+        frame.record("encode", Duration::from_millis(3));
+        let value = frame.stage("present", || 7);
+        let timing = frame.finish(Duration::from_millis(10));
+
+        assert_eq!(value, 7);
+        assert_eq!(timing.frame, 3);
+        let names = timing
+            .stages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ["encode", "submit", "present"]);
+        assert_eq!(timing.stage("encode"), Duration::from_millis(4));
+        assert_eq!(timing.stage("upload"), Duration::ZERO);
+    }
+}
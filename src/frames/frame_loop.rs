@@ -0,0 +1,178 @@
+use log::debug;
+use std::time::{Duration, Instant};
+
+use super::{Frame, FrameStats, FrameTimes};
+use crate::models::{Benchmark, BenchmarkFn, BenchmarkMetadata, Measurements};
+use crate::Result;
+
+/// When a FrameLoop stops.
+#[derive(Debug, Clone, Copy)]
+enum FrameLimit {
+    Frames(usize),
+    Duration(Duration),
+}
+
+/// Calls a callback once per frame, for a number of frames or a duration,
+/// timing every frame and the stages the callback marks on it.
+#[derive(Debug, Clone)]
+pub struct FrameLoop {
+    limit: FrameLimit,
+    warmup: usize,
+    stutter_factor: f64,
+}
+
+impl Default for FrameLoop {
+    /// Generates a FrameLoop type with:
+    /// + frames: 600
+    /// + warmup: 10
+    /// + stutter_factor: 2.0
+    fn default() -> Self {
+        FrameLoop {
+            limit: FrameLimit::Frames(600),
+            warmup: 10,
+            stutter_factor: 2.0,
+        }
+    }
+}
+
+impl FrameLoop {
+    pub fn new() -> Self {
+        FrameLoop::default()
+    }
+
+    /// Run for @param frames frames.
+    pub fn frames(mut self, frames: usize) -> Self {
+        self.limit = FrameLimit::Frames(frames);
+        self
+    }
+
+    /// Run for as many frames as complete within @param duration.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.limit = FrameLimit::Duration(duration);
+        self
+    }
+
+    /// Number of untimed frames run first, e.g. to fill caches.
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Frames over @param factor times the median frame time count as
+    /// stutters.
+    pub fn stutter_factor(mut self, factor: f64) -> Self {
+        self.stutter_factor = factor;
+        self
+    }
+
+    /// Calls @param callback once per frame, stopping at its first error.
+    pub fn run<F>(&self, mut callback: F) -> Result<FrameTimes>
+    where
+        F: FnMut(&mut Frame) -> Result<()>,
+    {
+        for _ in 0..self.warmup {
+            callback(&mut Frame::new(0))?;
+        }
+
+        let mut frames = Vec::new();
+        let start = Instant::now();
+        loop {
+            let done = match self.limit {
+                FrameLimit::Frames(n) => frames.len() >= n,
+                FrameLimit::Duration(d) => start.elapsed() >= d,
+            };
+            if done {
+                break;
+            }
+            let mut frame = Frame::new(frames.len());
+            let frame_start = Instant::now();
+            callback(&mut frame)?;
+            frames.push(frame.finish(frame_start.elapsed()));
+        }
+        debug!("ran {} frames in {:?}", frames.len(), start.elapsed());
+        Ok(FrameTimes::new(frames))
+    }
+
+    /// Calls @param callback once per frame and computes FrameStats.
+    pub fn stats<F>(&self, callback: F) -> Result<Measurements<FrameStats>>
+    where
+        F: FnMut(&mut Frame) -> Result<()>,
+    {
+        Ok(self.run(callback)?.stats(self.stutter_factor))
+    }
+
+    /// Benchmark named @param name running @param callback in this loop,
+    /// measuring the FrameStats of its frames and stages.
+    pub fn benchmark<S, F>(self, name: S, callback: F) -> Benchmark<FrameStats>
+    where
+        S: Into<String>,
+        F: FnMut(&mut Frame) -> Result<()> + 'static,
+    {
+        let metadata = BenchmarkMetadata::new(name);
+        Benchmark::new(metadata, BenchmarkFn::new(move || self.stats(callback)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn warmup_frames_are_not_timed() -> Result<()> {
+        let mut calls = 0;
+        let times = FrameLoop::new().warmup(3).frames(5).run(|frame| {
+            calls += 1;
+            let stage = match calls <= 3 {
+                true => "warmup",
+                false => "draw",
+            };
+            frame.record(stage, Duration::from_micros(1));
+            Ok(())
+        })?;
+
+        assert_eq!(calls, 8);
+        assert_eq!(times.len(), 5);
+        let indices = times
+            .frames()
+            .iter()
+            .map(|frame| frame.frame)
+            .collect::<Vec<usize>>();
+        assert_eq!(indices, [0, 1, 2, 3, 4]);
+        assert_eq!(times.stage_names(), ["draw"]);
+        Ok(())
+    }
+
+    #[test]
+    fn duration_limits_the_frames() -> Result<()> {
+        let frame_time = Duration::from_millis(2);
+        let times = FrameLoop::new()
+            .warmup(0)
+            .duration(Duration::from_millis(20))
+            .run(|_| {
+                std::thread::sleep(frame_time);
+                Ok(())
+            })?;
+
+        // Every frame starts within the duration
+        assert!((1..=10).contains(&times.len()), "{} frames", times.len());
+        assert!(times.frames().iter().all(|f| f.frame_time >= frame_time));
+        Ok(())
+    }
+
+    #[test]
+    fn run_stops_at_the_first_error() {
+        let mut calls = 0;
+        let result = FrameLoop::new().warmup(0).frames(5).run(|_| {
+            calls += 1;
+            match calls {
+                3 => Err(anyhow!("lost device")),
+                _ => Ok(()),
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+}
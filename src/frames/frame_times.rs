@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use super::FrameTiming;
+use crate::analysis::statistics;
+use crate::macros::measurement;
+use crate::models::{Measurements, Record, Value};
+
+/// Distribution of the times of one series, either whole frames or one
+/// stage, over the frames of a FrameLoop run.
+#[measurement]
+pub struct FrameStats {
+    /// `frame`, or the name of a stage.
+    pub series: String,
+    pub frames: usize,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub p99_ns: f64,
+    pub max_ns: f64,
+    /// Frames per second at the mean time.
+    pub mean_fps: f64,
+    /// Frames per second at the mean time of the slowest 1% of frames.
+    pub low_1_percent_fps: f64,
+    /// Frames slower than the stutter factor times the median.
+    pub stutters: usize,
+}
+
+/// Times of the frames of one FrameLoop run.
+#[derive(Debug, Clone)]
+pub struct FrameTimes {
+    frames: Vec<FrameTiming>,
+}
+
+impl FrameTimes {
+    pub fn new(frames: Vec<FrameTiming>) -> Self {
+        FrameTimes { frames }
+    }
+
+    pub fn frames(&self) -> &[FrameTiming] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Names of every stage, in the order they first ran.
+    pub fn stage_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for frame in &self.frames {
+            for (name, _) in &frame.stages {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    /// One record per frame of its `frame` index, `frame_time_ns` and
+    /// `<stage>_ns` for every stage, zero where a stage did not run.
+    pub fn records(&self) -> Measurements<Record> {
+        let names = self.stage_names();
+        self.frames
+            .iter()
+            .map(|frame| {
+                let mut record = Record::new();
+                record.push("frame", Value::Int(frame.frame as i64));
+                let frame_time = frame.frame_time.as_nanos() as i64;
+                record.push("frame_time_ns", Value::Int(frame_time));
+                for name in &names {
+                    let time = frame.stage(name).as_nanos() as i64;
+                    record.push(format!("{name}_ns"), Value::Int(time));
+                }
+                record
+            })
+            .collect()
+    }
+
+    /// FrameStats of the whole frames, then of every stage, counting times
+    /// over @param stutter_factor times the median as stutters.
+    pub fn stats(&self, stutter_factor: f64) -> Measurements<FrameStats> {
+        let mut stats = Measurements::new();
+        let frame_times =
+            self.frames.iter().map(|frame| frame.frame_time).collect();
+        stats.push(FrameStats::of("frame", frame_times, stutter_factor));
+        for name in self.stage_names() {
+            let times = self.frames.iter().map(|frame| frame.stage(&name));
+            let times = times.collect();
+            stats.push(FrameStats::of(name, times, stutter_factor));
+        }
+        stats
+    }
+}
+
+impl FrameStats {
+    fn of<S>(series: S, times: Vec<Duration>, stutter_factor: f64) -> Self
+    where
+        S: Into<String>,
+    {
+        let ns = times
+            .iter()
+            .map(|time| time.as_nanos() as f64)
+            .collect::<Vec<f64>>();
+        let mean_ns = statistics::mean(&ns);
+        let median_ns = statistics::median(&ns);
+
+        // Mean of the slowest 1% of times, at least one
+        let mut slowest = ns.clone();
+        slowest.sort_by(|a, b| b.total_cmp(a));
+        slowest.truncate((ns.len() / 100).max(1));
+        let low_ns = statistics::mean(&slowest);
+
+        let fps = |ns: f64| if ns > 0.0 { 1e9 / ns } else { 0.0 };
+        FrameStats {
+            series: series.into(),
+            frames: ns.len(),
+            mean_ns,
+            median_ns,
+            p99_ns: statistics::percentile(&ns, 99.0),
+            max_ns: ns.iter().copied().fold(f64::NAN, f64::max),
+            mean_fps: fps(mean_ns),
+            low_1_percent_fps: fps(low_ns),
+            stutters: ns
+                .iter()
+                .filter(|&&ns| ns > stutter_factor * median_ns)
+                .count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn stats_of_known_frame_times() {
+        // 198 frames of 10ms and 2 stutters of 50ms
+        let mut times = vec![ms(10); 198];
+        times.extend([ms(50), ms(50)]);
+        let stats = FrameStats::of("frame", times, 2.0);
+
+        assert_eq!(stats.frames, 200);
+        assert_eq!(stats.median_ns, 10e6);
+        assert_eq!(stats.max_ns, 50e6);
+        assert!((stats.mean_ns - 10.4e6).abs() < 1e-3);
+        assert!((stats.mean_fps - 1e9 / 10.4e6).abs() < 1e-9);
+        // The slowest 1% are the 2 stutters
+        assert!((stats.low_1_percent_fps - 20.0).abs() < 1e-9);
+        assert_eq!(stats.stutters, 2);
+    }
+
+    #[test]
+    fn low_fps_of_few_frames_is_the_slowest_frame() {
+        let stats = FrameStats::of("frame", vec![ms(10), ms(50), ms(20)], 2.0);
+
+        assert!((stats.low_1_percent_fps - 20.0).abs() < 1e-9);
+        assert_eq!(stats.stutters, 1);
+    }
+
+    #[test]
+    fn stats_of_no_frames_are_zero() {
+        let stats = FrameStats::of("frame", Vec::new(), 2.0);
+
+        assert_eq!(stats.frames, 0);
+        assert_eq!(stats.mean_fps, 0.0);
+        assert_eq!(stats.low_1_percent_fps, 0.0);
+        assert_eq!(stats.stutters, 0);
+    }
+
+    #[test]
+    fn stats_cover_the_frames_then_every_stage() {
+        let timing = |frame, stages: &[(&str, u64)]| FrameTiming {
+            frame,
+            frame_time: ms(10),
+            stages: stages
+                .iter()
+                .map(|(name, time)| (name.to_string(), ms(*time)))
+                .collect(),
+        };
+        let times = FrameTimes::new(vec![
+            timing(0, &[("encode", 2)]),
+            timing(1, &[("encode", 4), ("present", 6)]),
+        ]);
+        let stats = times.stats(2.0).into_iter().collect::<Vec<FrameStats>>();
+
+        let series =
+            stats.iter().map(|s| s.series.as_str()).collect::<Vec<_>>();
+        assert_eq!(series, ["frame", "encode", "present"]);
+        // A stage that did not run in a frame took no time in it
+        assert_eq!(stats[2].median_ns, 3e6);
+        let records = times.records().into_iter().collect::<Vec<Record>>();
+        assert_eq!(records[0].get("present_ns"), Some(&Value::Int(0)));
+    }
+}
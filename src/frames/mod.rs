@@ -0,0 +1,12 @@
+//! Frame-loop benchmarking, timing every frame and its named stages.
+
+mod frame;
+pub use frame::Frame;
+pub use frame::FrameTiming;
+
+mod frame_loop;
+pub use frame_loop::FrameLoop;
+
+mod frame_times;
+pub use frame_times::FrameStats;
+pub use frame_times::FrameTimes;
@@ -0,0 +1,5 @@
+//! Procedural generators of SVG workloads.
+
+mod primitive;
+pub use primitive::Primitive;
+pub use primitive::PrimitiveGenerator;
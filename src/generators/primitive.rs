@@ -0,0 +1,305 @@
+use anyhow::anyhow;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::util;
+use crate::Result;
+
+/// Family of primitives, matching the bundled `assets/svg/primitives`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    /// Stroked straight line.
+    Line,
+    /// Filled quadratic curve.
+    Curve,
+    /// Filled cubic curve.
+    CubicCurve,
+    /// Filled triangle at a random rotation.
+    Triangle,
+    /// Filled upright triangle, identical in every cell.
+    Triangles,
+    /// Filled quadrilateral.
+    Polygon,
+    /// Filled shape of two quadratic curves.
+    Bezigon,
+    /// Filled shape of two cubic curves.
+    CubicBezigon,
+}
+
+impl Primitive {
+    /// Every family, in the order of their names.
+    pub const ALL: [Primitive; 8] = [
+        Primitive::Bezigon,
+        Primitive::CubicBezigon,
+        Primitive::CubicCurve,
+        Primitive::Curve,
+        Primitive::Line,
+        Primitive::Polygon,
+        Primitive::Triangle,
+        Primitive::Triangles,
+    ];
+
+    /// Name of the family, as used in file names.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Primitive::Line => "line",
+            Primitive::Curve => "curve",
+            Primitive::CubicCurve => "cubic-curve",
+            Primitive::Triangle => "triangle",
+            Primitive::Triangles => "triangles",
+            Primitive::Polygon => "polygon",
+            Primitive::Bezigon => "bezigon",
+            Primitive::CubicBezigon => "cubic-bezigon",
+        }
+    }
+}
+
+impl FromStr for Primitive {
+    type Err = crate::Error;
+
+    /// Parses a family by name, e.g. `cubic-bezigon`.
+    fn from_str(s: &str) -> Result<Self> {
+        Primitive::ALL
+            .into_iter()
+            .find(|primitive| primitive.name() == s)
+            .ok_or_else(|| anyhow!("unknown primitive '{s}'"))
+    }
+}
+
+/// Generates SVG documents of a number of primitives of one family, laid out
+/// in a grid over the view box, with shapes drawn from a seeded RNG.
+#[derive(Debug, Clone)]
+pub struct PrimitiveGenerator {
+    primitive: Primitive,
+    count: usize,
+    width: f32,
+    height: f32,
+    size: f32,
+    seed: u64,
+}
+
+impl PrimitiveGenerator {
+    /// Generates a PrimitiveGenerator of @param primitive with:
+    /// + count: 1
+    /// + view box: 100 x 100
+    /// + size: 0.8
+    /// + seed: 0
+    pub fn new(primitive: Primitive) -> Self {
+        PrimitiveGenerator {
+            primitive,
+            count: 1,
+            width: 100.0,
+            height: 100.0,
+            size: 0.8,
+            seed: 0,
+        }
+    }
+
+    /// Number of primitives generated.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Size of the view box, `0 0 width height`.
+    pub fn view_box(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Size of each primitive relative to its grid cell, from 0 to 1.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Seed of the RNG; the same seed generates the same document.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// File name of the generated document, e.g. `bezigon-100.svg`.
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.svg", self.primitive.name(), self.count)
+    }
+
+    /// The generated SVG document.
+    pub fn svg(&self) -> String {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let columns = (self.count as f32).sqrt().ceil().max(1.0) as usize;
+        let rows = self.count.div_ceil(columns).max(1);
+        let cell_width = self.width / columns as f32;
+        let cell_height = self.height / rows as f32;
+        let cell = cell_width.min(cell_height);
+        let radius = self.size * cell / 2.0;
+
+        let mut svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 {} {}'>\n",
+            self.width, self.height
+        );
+        for i in 0..self.count {
+            let center = (
+                (i % columns) as f32 * cell_width + cell_width / 2.0,
+                (i / columns) as f32 * cell_height + cell_height / 2.0,
+            );
+            let rotation = rng.gen_range(0.0..2.0 * PI);
+            let path = self.path(center, radius, rotation, cell);
+            let _ = writeln!(svg, "    {path}");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the generated document to @param path.
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            util::io::dir_create_all(parent)?;
+        }
+        std::fs::write(path, self.svg())?;
+        Ok(())
+    }
+
+    /// Writes the generated document into @param dir under its file name,
+    /// returning its path.
+    pub fn write_to_dir<P>(&self, dir: P) -> Result<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        let path = dir.as_ref().join(self.file_name());
+        self.write(&path)?;
+        Ok(path)
+    }
+
+    /// Path element of one primitive inscribed in the circle at @param center
+    /// of @param radius, within a grid cell of size @param cell.
+    fn path(
+        &self,
+        center: (f32, f32),
+        radius: f32,
+        rotation: f32,
+        cell: f32,
+    ) -> String {
+        // Corners of the rotated square inscribed in the circle
+        let corner = |i: usize| {
+            let angle = rotation + PI / 4.0 + i as f32 * FRAC_PI_2;
+            format!(
+                "{} {}",
+                center.0 + radius * angle.cos(),
+                center.1 + radius * angle.sin()
+            )
+        };
+        let (c0, c1, c2, c3) = (corner(0), corner(1), corner(2), corner(3));
+        let d = match self.primitive {
+            Primitive::Line => {
+                return format!(
+                    "<path d='M{c0} L{c2} Z' stroke='black' stroke-width='{}'/>",
+                    cell / 5.0
+                );
+            }
+            Primitive::Curve => format!("M{c0} Q{c1} {c2} Z"),
+            Primitive::CubicCurve => format!("M{c0} C{c1} {c3} {c2} Z"),
+            Primitive::Triangle => {
+                let vertex = |i: usize| {
+                    let angle = rotation + i as f32 * 2.0 * PI / 3.0;
+                    format!(
+                        "{} {}",
+                        center.0 + radius * angle.cos(),
+                        center.1 + radius * angle.sin()
+                    )
+                };
+                format!("M{} L{} L{} Z", vertex(0), vertex(1), vertex(2))
+            }
+            Primitive::Triangles => {
+                let (x, y) = center;
+                format!(
+                    "M{x} {} L{} {} L{} {} Z",
+                    y - radius,
+                    x - radius,
+                    y + radius,
+                    x + radius,
+                    y + radius
+                )
+            }
+            Primitive::Polygon => format!("M{c0} L{c1} L{c2} L{c3} Z"),
+            Primitive::Bezigon => format!("M{c0} Q{c1} {c2} Q{c3} {c0} Z"),
+            Primitive::CubicBezigon => {
+                format!("M{c0} C{c1} {c3} {c2} C{c1} {c3} {c0} Z")
+            }
+        };
+        format!("<path d='{d}'/>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use usvg::{NodeKind, Tree};
+
+    use super::*;
+
+    fn paths(svg: &str) -> Result<usize> {
+        let tree = Tree::from_str(svg, &usvg::Options::default().to_ref())?;
+        Ok(tree
+            .root()
+            .descendants()
+            .filter(|node| matches!(*node.borrow(), NodeKind::Path(_)))
+            .count())
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for primitive in Primitive::ALL {
+            assert_eq!(
+                primitive.name().parse::<Primitive>().ok(),
+                Some(primitive)
+            );
+        }
+        let mut names = Primitive::ALL.map(|primitive| primitive.name());
+        names.sort_unstable();
+        assert_eq!(names, Primitive::ALL.map(|primitive| primitive.name()));
+        assert!("square".parse::<Primitive>().is_err());
+    }
+
+    #[test]
+    fn seed_determines_the_document() {
+        let generator = PrimitiveGenerator::new(Primitive::Triangle).count(10);
+        assert_eq!(generator.svg(), generator.clone().svg());
+        assert_eq!(
+            generator.clone().seed(7).svg(),
+            generator.clone().seed(7).svg()
+        );
+        assert_ne!(generator.clone().seed(7).svg(), generator.svg());
+    }
+
+    #[test]
+    fn count_primitives_are_generated() -> Result<()> {
+        for primitive in Primitive::ALL {
+            let generator = PrimitiveGenerator::new(primitive).count(7);
+            let svg = generator.svg();
+            assert_eq!(svg.matches("<path").count(), 7, "{svg}");
+            assert_eq!(paths(&svg)?, 7, "{svg}");
+            assert_eq!(
+                generator.file_name(),
+                format!("{}-7.svg", primitive.name())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn zero_count_generates_an_empty_document() -> Result<()> {
+        let svg = PrimitiveGenerator::new(Primitive::Line).count(0).svg();
+        assert_eq!(svg.matches("<path").count(), 0);
+        assert_eq!(paths(&svg)?, 0);
+        Ok(())
+    }
+}
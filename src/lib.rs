@@ -3,17 +3,31 @@
 #![feature(div_duration)]
 #![feature(duration_consts_float)]
 
+#[cfg(feature = "nvtx")]
 pub extern crate nvtx;
 
 pub use anyhow::Error;
 pub use anyhow::Result;
 
+pub mod analysis;
+pub mod annotate;
+pub mod cli;
+pub mod frames;
+pub mod generators;
 pub mod macros;
 pub mod models;
 pub mod monitors;
+pub mod plotters;
+pub mod profilers;
+pub mod rendering;
+pub mod report;
+pub mod suites;
+pub mod tracers;
 pub mod util;
+pub mod writers;
 
 pub mod prelude {
+    #[cfg(feature = "nvtx")]
     pub extern crate nvtx;
     pub use crate::macros::*;
     pub use crate::models::*;
@@ -1,3 +1,4 @@
+pub use crate::{mark, span};
 pub use proc_macro_measurable::measurement;
 
 #[macro_export]
@@ -24,3 +25,20 @@ macro_rules! log_assert {
         }
     );
 }
+
+/// Starts an `annotate::Span`, named like `format!`, ending when the returned
+/// guard is dropped.
+#[macro_export]
+macro_rules! span {
+    ($($name:tt)+) => {
+        $crate::annotate::Span::enter(::std::format!($($name)+))
+    };
+}
+
+/// Marks an instant with `annotate::mark`, named like `format!`.
+#[macro_export]
+macro_rules! mark {
+    ($($name:tt)+) => {
+        $crate::annotate::mark(::std::format!($($name)+))
+    };
+}
@@ -7,12 +7,15 @@ use std::sync::{Arc, Barrier, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use crate::analysis::AccuracyCheck;
+use crate::annotate::{self, Annotation};
 use crate::models::{
-    BenchmarkBundle, BenchmarkFn, BenchmarkMetadata, DriverOptions, Measurable,
-    Measurement, Measurements, Monitor, MonitorBundle,
+    AccuracyBundle, BenchmarkBundle, BenchmarkFn, BenchmarkMetadata,
+    DriverOptions, Measurable, Measurement, Measurements, Monitor,
+    MonitorBundle,
 };
-use crate::util;
 use crate::Result;
+use crate::{tracers, util};
 
 /// Benchmark
 pub struct Benchmark<T>
@@ -22,6 +25,7 @@ where
     metadata: BenchmarkMetadata,
     func: Option<BenchmarkFn<T>>,
     monitors: Vec<Box<dyn Monitor>>,
+    accuracy_checks: Vec<AccuracyCheck>,
 }
 
 impl<T> From<BenchmarkFn<T>> for Benchmark<T>
@@ -43,6 +47,7 @@ where
             metadata: data,
             func: Some(func),
             monitors: vec![],
+            accuracy_checks: vec![],
         }
     }
 
@@ -55,6 +60,25 @@ where
         self
     }
 
+    /// Add an AccuracyCheck of the images rendered by current Benchmarks,
+    /// run once they complete.
+    pub fn accuracy(mut self, check: AccuracyCheck) -> Self {
+        self.accuracy_checks.push(check);
+        self
+    }
+
+    /// Erase the Measurable type of this Benchmark, so Benchmarks of different
+    /// types can share a Driver.
+    pub fn erased(mut self) -> Benchmark<Measurement> {
+        let func = self.func.take().expect("How was this taken?");
+        Benchmark {
+            metadata: self.metadata,
+            func: Some(func.erased()),
+            monitors: self.monitors,
+            accuracy_checks: self.accuracy_checks,
+        }
+    }
+
     /// Read only reference for metadata of current Benchmark instance.
     pub fn metadata(&self) -> &BenchmarkMetadata {
         &self.metadata
@@ -88,10 +112,14 @@ where
         let start_time = Instant::now();
 
         // Collect monitor measurements
+        let tracer = tracers::active();
         let monitor_measurement_map =
             HashMap::<String, Measurements<Measurement>>::new();
         let mmm_arc = Arc::new(Mutex::new(monitor_measurement_map));
-        let scope: Result<Measurements<T>, anyhow::Error> =
+        let scope: Result<
+            (Measurements<T>, Measurements<Annotation>, Duration),
+            anyhow::Error,
+        > =
             crossbeam::scope(|scope| {
                 for mon in self.monitors.iter_mut() {
                     scope.spawn(|_| {
@@ -113,7 +141,9 @@ where
 
                         // Poll
                         let poll_start_time = Instant::now();
+                        tracer.range_push(&format!("{mon_name} poll"));
                         let measurable = mon.poll();
+                        tracer.range_pop();
                         let poll_end_time = Instant::now();
                         let elapsed = poll_end_time - poll_start_time;
 
@@ -134,7 +164,8 @@ where
                             match measurable {
                                 Ok(measurable) => {
                                     debug!("{mon_name}: polled in {elapsed:?}");
-                                    monitor_measurements.push(measurable);
+                                    let since_start = poll_start_time - start_time;
+                                    monitor_measurements.push(measurable.timestamped(since_start));
                                 },
                                 Err(e) => error!("{mon_name}: failed to poll with error '{e}'")
                             }
@@ -150,16 +181,19 @@ where
                 barrier.wait();
                 trace!("{bm_name}: starting execution");
                 let func = self.func.take().expect("How was this taken?");
-                let measurements = func.run(&bm_name)?;
+                annotate::collect();
+                let measurements = func.run(&bm_name);
+                let duration = Instant::now() - start_time;
+                let annotations = annotate::take();
                 trace!("{bm_name}: completed execution");
                 complete.store(true, Ordering::Release);
 
                 // Return results
-                Ok(measurements)
+                Ok((measurements?, annotations, duration))
             }).map_err(|thread_ex| {
             anyhow!("Unit thread exception: {thread_ex:?}")
         })?;
-        let measurements = scope?;
+        let (measurements, annotations, duration) = scope?;
         let monitor_measurements = Arc::try_unwrap(mmm_arc)
             .expect("No one should hold this arc!")
             .into_inner()
@@ -172,10 +206,28 @@ where
         self.monitor_lifecycle_hook("on_stop", |mon| Ok(mon.on_stop()))?;
         trace!("{bm_name}: stopped all monitors");
 
+        // Check accuracy of rendered images
+        let mut accuracy_bundle = AccuracyBundle::default();
+        for check in &self.accuracy_checks {
+            // Measurements are kept even if images cannot be checked
+            if let Err(e) = check.run(&mut accuracy_bundle) {
+                error!("{bm_name}: failed to check accuracy with error '{e}'");
+            }
+        }
+        if !accuracy_bundle.is_empty() {
+            debug!(
+                "{bm_name}: checked accuracy of {} images",
+                accuracy_bundle.measurements.len()
+            );
+        }
+
         // Package bundle
         let bundle = BenchmarkBundle {
             measurements,
+            annotations,
             monitor_bundle,
+            accuracy_bundle,
+            duration,
         };
 
         Ok(bundle)
@@ -201,6 +253,7 @@ where
 
         // Run monitor lifecycle hook
         let results_ref = results.clone();
+        let tracer = tracers::active();
         let barrier = Barrier::new(self.monitors.len());
         crossbeam::scope(|scope| {
             // Spawn threads
@@ -216,7 +269,10 @@ where
                         "{mon_name}: released from '{lifecycle_name}' lifecycle barrier"
                     );
                     // Get result from given logic
-                    let result = func(mon)?;
+                    tracer.range_push(&format!("{mon_name} {lifecycle_name}"));
+                    let result = func(mon);
+                    tracer.range_pop();
+                    let result = result?;
                     // Append results
                     let mut results_lock = results_ref.lock().unwrap();
                     results_lock.insert(mon_name, result);
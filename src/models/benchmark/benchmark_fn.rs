@@ -1,4 +1,5 @@
-use crate::models::{Measurable, Measurements};
+use crate::models::{Measurable, Measurement, Measurements};
+use crate::tracers;
 use crate::Result;
 
 /// Boxed dyn FnOnce type producing a Result<Measurable<T>>.
@@ -40,11 +41,12 @@ where
     where
         S: Into<String>,
     {
-        nvtx::range_push(
+        let tracer = tracers::active();
+        tracer.range_push(
             format!("benching {name}", name = name.into()).as_str(),
         );
         let result = self.extract();
-        nvtx::range_pop();
+        tracer.range_pop();
         result
     }
 
@@ -54,4 +56,11 @@ where
     {
         BenchmarkFn(Box::new(func))
     }
+
+    /// Erase the Measurable type of the measurements produced.
+    pub fn erased(self) -> BenchmarkFn<Measurement> {
+        BenchmarkFn::new(move || {
+            Ok(self.extract()?.into_iter().map(Measurement::from).collect())
+        })
+    }
 }
@@ -1,15 +1,17 @@
-
 /// Type storing descriptive data for a Benchmark instance.
 pub struct BenchmarkMetadata {
-    name: &'static str,
+    name: String,
 }
 
 impl BenchmarkMetadata {
-    pub fn new(name: &'static str) -> Self {
-        BenchmarkMetadata { name }
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        BenchmarkMetadata { name: name.into() }
     }
 
-    pub fn name(&self) -> &'static str {
-        self.name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 }
@@ -1,6 +1,25 @@
-use std::{collections::HashMap, path::Path};
+use log::{trace, warn};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use crate::models::{Measurable, Measurement, Measurements};
+use crate::analysis::{
+    BenchmarkSummary, ImageDiffMeasurement, MetricSummary, SpanSummary,
+};
+use crate::annotate::Annotation;
+use crate::models::{
+    Environment, Measurable, Measurement, Measurements, Plotter, Record, Table,
+    Value, Writer,
+};
+use crate::plotters::{
+    BoxPlotter, HistogramPlotter, PlotOptions, ScalingPlotter,
+    TimeSeriesPlotter, ViolinPlotter,
+};
+use crate::rendering::RgbaImage;
+use crate::util;
 use crate::Result;
 
 /// HashMap of <String, Measurements> types
@@ -10,19 +29,160 @@ pub struct MonitorBundle {
 }
 
 impl MonitorBundle {
-    pub fn write<P>(&self, path: P) -> Result<()>
+    /// Writes each monitor's measurements to `<name>` in @param path with
+    /// @param writer, which chooses the file extension.
+    pub fn write<P, W>(&self, path: P, writer: &W) -> Result<()>
     where
         P: AsRef<Path>,
+        W: Writer + ?Sized,
     {
         let path = path.as_ref();
         for (name, monitor) in &self.monitor_measurements {
-            let mut data_path = path.join(name);
-            data_path.set_extension("csv");
-            monitor.write(data_path)?;
+            writer.write(monitor, path.join(name))?;
         }
 
         Ok(())
     }
+
+    /// Reads every `<monitor>.csv` in @param path, as written by `write` with
+    /// `CsvWriter`.
+    /// Monitor data is untyped, so each row is read as a Record.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut monitor_measurements = HashMap::new();
+        for file in util::io::get_files_with_extension(path, false, "csv") {
+            let name = match file.file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => continue,
+            };
+            let records = Measurements::<Record>::read(&file)?;
+            let measurements = records.into_iter().map(Measurement::from);
+            monitor_measurements.insert(name, measurements.collect());
+        }
+
+        Ok(MonitorBundle {
+            monitor_measurements,
+        })
+    }
+
+    /// Plots each monitor's numeric series over time to `<name>.<extension>`
+    /// in @param path, marking the end of a benchmark lasting
+    /// @param duration. Monitors without numeric samples are skipped.
+    pub fn plot<P>(
+        &self,
+        path: P,
+        extension: &str,
+        duration: Duration,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        for (name, table) in self.tables()? {
+            // Monitor names such as `gpu.0` contain dots, which
+            // with_extension would replace
+            let file = path.join(format!("{name}.{extension}"));
+            save_plot(MonitorBundle::timeline(&name, duration), &table, file);
+        }
+
+        Ok(())
+    }
+
+    /// Each monitor's samples as a Table, ordered by name. Monitors without
+    /// numeric samples are skipped.
+    pub fn tables(&self) -> Result<Vec<(String, Table)>> {
+        let mut names = self.monitor_measurements.keys().collect::<Vec<_>>();
+        names.sort();
+        let mut tables = Vec::new();
+        for name in names {
+            let table =
+                Table::from_measurements(&self.monitor_measurements[name])?;
+            if table.numeric_columns().count() < 2 {
+                trace!("{name}: no numeric samples to plot, skipping");
+                continue;
+            }
+            tables.push((name.clone(), table));
+        }
+        Ok(tables)
+    }
+
+    /// Timeline of a monitor's samples, marking the start and, when known,
+    /// the end of a benchmark lasting @param duration.
+    pub(crate) fn timeline(
+        name: &str,
+        duration: Duration,
+    ) -> TimeSeriesPlotter {
+        let plotter = TimeSeriesPlotter::new().title(name).marker("start", 0.0);
+        match duration.is_zero() {
+            true => plotter,
+            false => plotter.marker("end", duration.as_nanos() as f64),
+        }
+    }
+}
+
+/// Accuracy of the images rendered by a benchmark, with a diff heatmap per
+/// image name.
+#[derive(Debug)]
+pub struct AccuracyBundle {
+    pub measurements: Measurements<ImageDiffMeasurement>,
+    pub heatmaps: HashMap<String, RgbaImage>,
+}
+
+impl Default for AccuracyBundle {
+    /// Generates an empty AccuracyBundle.
+    fn default() -> Self {
+        AccuracyBundle {
+            measurements: Measurements::new(),
+            heatmaps: HashMap::new(),
+        }
+    }
+}
+
+impl AccuracyBundle {
+    pub fn is_empty(&self) -> bool {
+        self.measurements.is_empty() && self.heatmaps.is_empty()
+    }
+
+    /// Writes the measurements to `accuracy` with @param writer, and each
+    /// heatmap to `diffs/<name>` as PNG, in @param path. Nothing is written
+    /// when empty.
+    pub fn write<P, W>(&self, path: P, writer: &W) -> Result<()>
+    where
+        P: AsRef<Path>,
+        W: Writer + ?Sized,
+    {
+        let path = path.as_ref();
+        if !self.measurements.is_empty() {
+            writer.write(&self.measurements, path.join("accuracy"))?;
+        }
+        for (name, heatmap) in &self.heatmaps {
+            let file = path.join("diffs").join(name).with_extension("png");
+            heatmap.write_png(file)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads `accuracy.csv` in @param path, as written by `write` with
+    /// `CsvWriter`, if present. Heatmaps are not read back.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().join("accuracy.csv");
+        let measurements = match path.is_file() {
+            true => Measurements::read(path)?,
+            false => Measurements::new(),
+        };
+
+        Ok(AccuracyBundle {
+            measurements,
+            heatmaps: HashMap::new(),
+        })
+    }
 }
 
 /// Unifies Measurements and MonitorBundles for further processing.
@@ -32,55 +192,429 @@ where
     T: Measurable,
 {
     pub measurements: Measurements<T>,
+    /// Spans and marks of the benchmark's code, see `annotate`.
+    pub annotations: Measurements<Annotation>,
     pub monitor_bundle: MonitorBundle,
+    /// Accuracy of rendered images, empty unless the benchmark has an
+    /// AccuracyCheck.
+    pub accuracy_bundle: AccuracyBundle,
+    /// Time from the start of the benchmark, which monitor timestamps are
+    /// relative to, until its measurements were returned.
+    pub duration: Duration,
 }
 
 impl<T> BenchmarkBundle<T>
 where
     T: Measurable,
 {
-    pub fn write<P>(&self, path: P) -> Result<()>
+    /// Writes `measurements`, any `annotations` with their `spans` summarised
+    /// by name, `monitors/<name>` and any accuracy results in @param path
+    /// with @param writer, which chooses the file extension.
+    pub fn write<P, W>(&self, path: P, writer: &W) -> Result<()>
     where
         P: AsRef<Path>,
+        W: Writer + ?Sized,
     {
         let path = path.as_ref();
 
-        let mut data_path = path.to_owned();
-        data_path.push("measurements");
-        data_path.set_extension("csv");
-        self.measurements.write(&data_path)?;
+        writer.write(&self.measurements, path.join("measurements"))?;
+        if !self.annotations.is_empty() {
+            writer.write(&self.annotations, path.join("annotations"))?;
+            let spans: Measurements<SpanSummary> =
+                SpanSummary::of_annotations(&self.annotations)
+                    .into_iter()
+                    .collect();
+            if !spans.is_empty() {
+                writer.write(&spans, path.join("spans"))?;
+            }
+        }
 
         // Write monitor measurements
         let mon_path = path.join("monitors");
-        self.monitor_bundle.write(mon_path)?;
+        self.monitor_bundle.write(mon_path, writer)?;
+
+        self.accuracy_bundle.write(path, writer)?;
+
+        Ok(())
+    }
+
+    /// Reads a benchmark directory written by `write`, deserializing
+    /// `measurements.csv` into T. Use `Record` as T for untyped data.
+    /// The duration is recorded by the run, so it reads as zero.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        T: DeserializeOwned,
+    {
+        let path = path.as_ref();
+        trace!("reading benchmark bundle from {path:?}");
+
+        let measurements = Measurements::read(path.join("measurements.csv"))?;
+        let annotations = match path.join("annotations.csv") {
+            file if file.is_file() => Measurements::read(file)?,
+            _ => Measurements::new(),
+        };
+        let monitor_bundle = MonitorBundle::read(path.join("monitors"))?;
+        let accuracy_bundle = AccuracyBundle::read(path)?;
+
+        Ok(BenchmarkBundle {
+            measurements,
+            annotations,
+            monitor_bundle,
+            accuracy_bundle,
+            duration: Duration::ZERO,
+        })
+    }
+
+    /// Plots a histogram and violin of every numeric field of `measurements`,
+    /// and the series of every monitor under `monitors/`, in @param path.
+    /// @param extension selects SVG or PNG output.
+    pub fn plot<P>(&self, path: P, extension: &str) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let table = Table::from_measurements(&self.measurements)?;
+        for column in table.numeric_columns() {
+            let name = column.name();
+            let file = path.join(format!("{name}_histogram.{extension}"));
+            save_plot(HistogramPlotter::new(name).title(name), &table, file);
+            let file = path.join(format!("{name}_violin.{extension}"));
+            save_plot(
+                ViolinPlotter::new().columns([name]).title(name),
+                &table,
+                file,
+            );
+        }
+
+        self.monitor_bundle.plot(
+            path.join("monitors"),
+            extension,
+            self.duration,
+        )?;
 
         Ok(())
     }
 }
 
-/// HashMap of <String, BenchmarkBundle> types
+/// HashMap of <String, BenchmarkBundle> types, alongside the errors of
+/// benchmarks which failed and the environment they ran in.
 #[derive(Debug)]
 pub struct DriverBundle<T>
 where
     T: Measurable,
 {
     pub benchmark_bundles: HashMap<String, BenchmarkBundle<T>>,
+    pub benchmark_failures: HashMap<String, String>,
+    pub environment: Environment,
 }
 
 impl<T> DriverBundle<T>
 where
     T: Measurable,
 {
-    pub fn write<P>(&self, path: P) -> Result<()>
+    /// Writes each BenchmarkBundle to `<name>` in @param path with
+    /// @param writer, followed by `environment`, the outcome of every
+    /// benchmark to `benchmarks` and their statistics to `summary`.
+    pub fn write<P, W>(&self, path: P, writer: &W) -> Result<()>
     where
         P: AsRef<Path>,
+        W: Writer + ?Sized,
     {
         let path = path.as_ref();
         for (name, bundle) in &self.benchmark_bundles {
             let bm_path = path.join(name);
-            bundle.write(bm_path)?;
+            bundle.write(bm_path, writer)?;
+        }
+
+        let environment = Measurements::from_iter([self.environment.clone()]);
+        writer.write(&environment, path.join("environment"))?;
+        writer.write(&self.benchmark_summaries(), path.join("benchmarks"))?;
+        writer.write(&self.metric_summaries()?, path.join("summary"))?;
+
+        Ok(())
+    }
+
+    /// Outcome of every benchmark, completed or failed, ordered by name.
+    pub fn benchmark_summaries(&self) -> Measurements<BenchmarkSummary> {
+        let completed = self.benchmark_bundles.iter().map(|(name, bundle)| {
+            BenchmarkSummary {
+                benchmark: name.clone(),
+                completed: true,
+                duration_ns: bundle.duration.as_nanos() as u64,
+                error: None,
+            }
+        });
+        let failed = self.benchmark_failures.iter().map(|(name, error)| {
+            BenchmarkSummary {
+                benchmark: name.clone(),
+                completed: false,
+                duration_ns: 0,
+                error: Some(error.clone()),
+            }
+        });
+        let mut summaries = completed.chain(failed).collect::<Vec<_>>();
+        summaries.sort_by(|a, b| a.benchmark.cmp(&b.benchmark));
+        summaries.into_iter().collect()
+    }
+
+    /// Statistics of every numeric measurement field, ordered by benchmark.
+    pub fn metric_summaries(&self) -> Result<Measurements<MetricSummary>> {
+        let mut names = self.benchmark_bundles.keys().collect::<Vec<_>>();
+        names.sort();
+        let mut summaries = Measurements::new();
+        for name in names {
+            let bundle = &self.benchmark_bundles[name];
+            let table = Table::from_measurements(&bundle.measurements)?;
+            for summary in MetricSummary::of_table(name, &table) {
+                summaries.push(summary);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Reads an output directory written by `write`, with one BenchmarkBundle
+    /// per benchmark directory. Durations and failures are restored from
+    /// `benchmarks.csv`, when present.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        T: DeserializeOwned,
+    {
+        let path = path.as_ref();
+        let mut benchmark_bundles = HashMap::new();
+        for entry in std::fs::read_dir(path)? {
+            let bm_path = entry?.path();
+            let is_benchmark = bm_path.join("measurements.csv").is_file()
+                || util::io::dir_exists(bm_path.join("monitors"));
+            if !is_benchmark {
+                continue;
+            }
+            if let Some(name) = bm_path.file_name() {
+                let name = name.to_string_lossy().to_string();
+                benchmark_bundles
+                    .insert(name, BenchmarkBundle::read(&bm_path)?);
+            }
+        }
+
+        let mut benchmark_failures = HashMap::new();
+        let summaries: Measurements<BenchmarkSummary> =
+            Measurements::read(path.join("benchmarks.csv"))?;
+        for summary in summaries {
+            if !summary.completed {
+                let error = summary.error.unwrap_or_default();
+                benchmark_failures.insert(summary.benchmark, error);
+            } else if let Some(bundle) =
+                benchmark_bundles.get_mut(&summary.benchmark)
+            {
+                bundle.duration = Duration::from_nanos(summary.duration_ns);
+            }
+        }
+
+        let environment: Measurements<Environment> =
+            Measurements::read(path.join("environment.csv"))?;
+        let environment = environment.into_iter().next().unwrap_or_default();
+
+        Ok(DriverBundle {
+            benchmark_bundles,
+            benchmark_failures,
+            environment,
+        })
+    }
+
+    /// Plots each BenchmarkBundle to `<name>` in @param path, and unless
+    /// disabled by @param options, a chart per measurement field comparing
+    /// all benchmarks. Charts that fail to plot are logged and skipped.
+    pub fn plot<P>(&self, path: P, options: &PlotOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        for format in &options.formats {
+            let extension = format.extension();
+            for (name, bundle) in &self.benchmark_bundles {
+                bundle.plot(path.join(name), extension)?;
+            }
+            if options.comparison && self.benchmark_bundles.len() > 1 {
+                self.plot_comparison(path, extension)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Charts every numeric measurement field across benchmarks. Benchmarks
+    /// whose names end in a number, such as a primitive count, are plotted
+    /// against it, with a line for each name prefix; other benchmarks are
+    /// left out. When no name ends in a number, each benchmark gets a box.
+    fn plot_comparison(&self, path: &Path, extension: &str) -> Result<()> {
+        let mut names = self.benchmark_bundles.keys().collect::<Vec<_>>();
+        names.sort();
+        let parameterized = names
+            .iter()
+            .any(|name| split_trailing_number(name).is_some());
+
+        // Long table of every measurement, tagged by benchmark and, when
+        // parameterized, by name prefix and parameter
+        let mut long = Table::new();
+        for name in names {
+            let parameter = split_trailing_number(name);
+            if parameterized && parameter.is_none() {
+                trace!("{name}: no trailing number to compare by, skipping");
+                continue;
+            }
+            let bundle = &self.benchmark_bundles[name];
+            let table = Table::from_measurements(&bundle.measurements)?;
+            for mut record in table.rows() {
+                record.push("benchmark", Value::Str(name.to_string()));
+                if let Some((prefix, number)) = parameter {
+                    let family = prefix.trim_end_matches(['_', '-']);
+                    record.push("family", Value::Str(family.to_owned()));
+                    record.push("parameter", Value::Int(number));
+                }
+                long.push(&record);
+            }
+        }
+
+        let fields = long
+            .numeric_columns()
+            .map(|c| c.name().to_owned())
+            .filter(|name| name != "parameter")
+            .collect::<Vec<String>>();
+        for field in fields {
+            let file = path.join(format!("comparison_{field}.{extension}"));
+            match parameterized {
+                true => save_plot(
+                    ScalingPlotter::new("parameter", &field)
+                        .series("family")
+                        .title(&field),
+                    &long,
+                    file,
+                ),
+                false => {
+                    // One column of values per benchmark
+                    let mut wide = Table::new();
+                    for row in long.rows() {
+                        if let (Some(Value::Str(name)), Some(value)) =
+                            (row.get("benchmark"), row.get(&field))
+                        {
+                            let record = [(name.clone(), value.clone())];
+                            wide.push(&record.into_iter().collect());
+                        }
+                    }
+                    save_plot(BoxPlotter::new().title(&field), &wide, file)
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Saves a plot of @param data to @param file, logging a failure rather than
+/// returning it so that one chart does not prevent the others.
+fn save_plot<P>(plotter: P, data: &Table, file: PathBuf)
+where
+    P: Plotter,
+{
+    if let Err(e) = plotter.save_plot(data, &file) {
+        warn!("{file:?}: could not plot, skipping: {e}");
+    }
+}
+
+/// Splits @param name into the prefix and the number it ends in, such as
+/// `triangles_` and `1000` for `triangles_1000`.
+fn split_trailing_number(name: &str) -> Option<(&str, i64)> {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = name[prefix.len()..].parse().ok()?;
+    Some((prefix, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    #[derive(Serialize, Debug)]
+    struct Frame {
+        time_ns: u64,
+    }
+
+    fn bundle(times: &[u64]) -> BenchmarkBundle<Frame> {
+        BenchmarkBundle {
+            measurements: times
+                .iter()
+                .map(|&time_ns| Frame { time_ns })
+                .collect(),
+            annotations: Measurements::new(),
+            monitor_bundle: MonitorBundle {
+                monitor_measurements: HashMap::new(),
+            },
+            accuracy_bundle: AccuracyBundle::default(),
+            duration: Duration::ZERO,
         }
+    }
+
+    #[test]
+    fn trailing_numbers_end_the_name() {
+        assert_eq!(
+            split_trailing_number("triangles_1000"),
+            Some(("triangles_", 1000))
+        );
+        assert_eq!(
+            split_trailing_number("v2_lines_10"),
+            Some(("v2_lines_", 10))
+        );
+        assert_eq!(split_trailing_number("v2_lines"), None);
+        assert_eq!(split_trailing_number("lines_10_aa"), None);
+    }
+
+    #[test]
+    fn comparison_draws_a_line_per_name_prefix() -> Result<()> {
+        let dir = TempDir::new("comparison");
+        let benchmark_bundles = [
+            ("circles_10", [1, 2]),
+            ("circles_100", [10, 20]),
+            ("squares_10", [3, 4]),
+            ("squares_100", [30, 40]),
+            ("tiger", [5, 6]),
+        ]
+        .into_iter()
+        .map(|(name, times)| (name.to_owned(), bundle(&times)))
+        .collect();
+        let driver_bundle = DriverBundle {
+            benchmark_bundles,
+            benchmark_failures: HashMap::new(),
+            environment: Environment::default(),
+        };
+        driver_bundle.plot_comparison(dir.as_ref(), "svg")?;
+
+        let svg = std::fs::read_to_string(dir.join("comparison_time_ns.svg"))?;
+        assert!(svg.contains("circles"), "{svg}");
+        assert!(svg.contains("squares"), "{svg}");
+        assert!(!svg.contains("tiger"), "{svg}");
+        Ok(())
+    }
+
+    #[test]
+    fn comparison_boxes_benchmarks_without_numbers() -> Result<()> {
+        let dir = TempDir::new("comparison-boxes");
+        let benchmark_bundles = [("lion", [1, 2]), ("tiger", [3, 4])]
+            .into_iter()
+            .map(|(name, times)| (name.to_owned(), bundle(&times)))
+            .collect();
+        let driver_bundle = DriverBundle {
+            benchmark_bundles,
+            benchmark_failures: HashMap::new(),
+            environment: Environment::default(),
+        };
+        driver_bundle.plot_comparison(dir.as_ref(), "svg")?;
 
+        let svg = std::fs::read_to_string(dir.join("comparison_time_ns.svg"))?;
+        assert!(svg.contains("lion") && svg.contains("tiger"), "{svg}");
         Ok(())
     }
 }
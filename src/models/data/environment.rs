@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Description of the machine and time a Driver run was executed on.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Environment {
+    /// Seconds since the UNIX epoch at which the run started.
+    pub timestamp: u64,
+    pub hostname: String,
+    pub os: String,
+    pub family: String,
+    pub arch: String,
+    pub cpus: usize,
+    /// Version of vgpu-bench which produced the run.
+    pub version: String,
+}
+
+impl Default for Environment {
+    /// Generates an Environment of unknown origin, for runs which did not
+    /// record one.
+    fn default() -> Self {
+        let unknown = String::from("unknown");
+        Environment {
+            timestamp: 0,
+            hostname: unknown.clone(),
+            os: unknown.clone(),
+            family: unknown.clone(),
+            arch: unknown.clone(),
+            cpus: 0,
+            version: unknown,
+        }
+    }
+}
+
+impl Environment {
+    /// Collect the environment of the current process.
+    pub fn collect() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Environment {
+            timestamp,
+            hostname: hostname(),
+            os: std::env::consts::OS.to_owned(),
+            family: std::env::consts::FAMILY.to_owned(),
+            arch: std::env::consts::ARCH.to_owned(),
+            cpus,
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+        }
+    }
+}
+
+fn hostname() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("unknown"))
+}
@@ -1,5 +1,6 @@
 use serde::{Serialize, Serializer};
 use std::fmt;
+use std::time::Duration;
 
 use crate::models::Measurable;
 
@@ -21,6 +22,22 @@ impl Measurement {
             measurable: Box::new(measurable),
         }
     }
+
+    /// Tag this Measurement with the time it was taken, relative to the start
+    /// of its Benchmark, as a leading `timestamp_ns` field.
+    pub fn timestamped(self, since_start: Duration) -> Self {
+        Measurement::from(TimestampedMeasurement {
+            timestamp_ns: since_start.as_nanos() as u64,
+            measurement: self,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct TimestampedMeasurement {
+    timestamp_ns: u64,
+    #[serde(flatten)]
+    measurement: Measurement,
 }
 
 /// Create a Measurement from a Boxed type.
@@ -1,11 +1,12 @@
+use serde::de::DeserializeOwned;
 use std::path::Path;
 
 use log::trace;
 use log::warn;
 
-use crate::log_assert;
 use crate::models::Measurable;
-use crate::util;
+use crate::models::Writer;
+use crate::writers::CsvWriter;
 use crate::Result;
 
 /// Wrapper for Vector of Measurable types.
@@ -39,36 +40,81 @@ where
         self.measurables.clear()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.measurables.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.measurables.iter()
+    }
+
     /// Writes a CSV containing the results of Measurements to @param path.
     /// This function overrides the file generated if it already existed previously.
     /// If Measurements is empty upon calling, nothing is written to the @param path. 
     pub fn write<P>(&self, path: P) -> Result<()>
     where
         P: AsRef<Path>,
+    {
+        CsvWriter.write(self, path)
+    }
+
+    /// Reads a CSV previously written by `write` from @param path.
+    /// A missing file is read as empty Measurements, since `write` skips
+    /// writing empty Measurements.
+    pub fn read<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        T: DeserializeOwned,
     {
         let mut path = path.as_ref().to_owned();
         path.set_extension("csv");
 
-        trace!("writing measurements to {path:?}");
+        trace!("reading measurements from {path:?}");
 
-        // Overwrite file if it exists
-        if path.exists() {
-            std::fs::remove_file(&path)?;
-            log_assert!(
-                path.exists() == false,
-                "{path:?} could not be removed"
-            );
+        let mut measurements = Measurements::new();
+        if !path.is_file() {
+            warn!("{path:?} does not exist, reading as empty");
+            return Ok(measurements);
+        }
+        let mut reader = csv::Reader::from_path(&path)?;
+        for row in reader.deserialize() {
+            measurements.push(row?);
         }
+        Ok(measurements)
+    }
+}
 
-        if self.measurables.is_empty() {
-            warn!("{path:?} no measurable to write, skipping");
-        } else {
-            let mut writer = util::io::csv_writer(path)?;
-            for row in &self.measurables {
-                writer.serialize(row)?;
-            }
-            writer.flush()?;
+impl<T> FromIterator<T> for Measurements<T>
+where
+    T: Measurable,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Measurements {
+            measurables: iter.into_iter().collect(),
         }
-        Ok(())
+    }
+}
+
+impl<T> IntoIterator for Measurements<T>
+where
+    T: Measurable,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.measurables.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Measurements<T>
+where
+    T: Measurable,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.measurables.iter()
     }
 }
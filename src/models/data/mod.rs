@@ -7,7 +7,26 @@ pub use measurements::Measurements;
 mod measurement;
 pub use measurement::Measurement;
 
+mod value;
+pub use value::Value;
+
+mod record;
+pub use record::Record;
+
+mod table;
+pub use table::Column;
+pub use table::Table;
+
+mod serializer;
+#[cfg(feature = "parquet")]
+pub(crate) use serializer::FieldType;
+pub use serializer::SerializeError;
+
+mod environment;
+pub use environment::Environment;
+
 mod bundles;
+pub use bundles::AccuracyBundle;
 pub use bundles::BenchmarkBundle;
 pub use bundles::DriverBundle;
 pub use bundles::MonitorBundle;
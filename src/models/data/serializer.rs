@@ -0,0 +1,783 @@
+use serde::ser::{self, Impossible, Serialize};
+use std::fmt::Display;
+use thiserror::Error;
+
+use crate::models::{Record, Value};
+
+/// Failure to flatten a Serialize type into named scalar fields.
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct SerializeError(String);
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerializeError(msg.to_string())
+    }
+}
+
+/// Type a field was serialized as, whatever its value: `Null` for `None`
+/// and units, and every integer type as `Int`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FieldType {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Str,
+}
+
+impl FieldType {
+    /// Type of a column holding fields of this type and @param other.
+    #[cfg(feature = "parquet")]
+    pub(crate) fn merge(self, other: FieldType) -> FieldType {
+        match (self, other) {
+            (FieldType::Null, t) | (t, FieldType::Null) => t,
+            (a, b) if a == b => a,
+            (FieldType::Int, FieldType::Float)
+            | (FieldType::Float, FieldType::Int) => FieldType::Float,
+            _ => FieldType::Str,
+        }
+    }
+}
+
+/// Flattens any Serialize type into a Record. Nested structs, maps and
+/// sequences produce dotted field names (e.g. `load.user`, `samples.0`), and
+/// a bare scalar produces a single field named `value`.
+pub(crate) fn to_record<T>(value: &T) -> Result<Record, SerializeError>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(to_typed_record(value)?.0)
+}
+
+/// Flattens @param value like to_record, along with the type of every field.
+pub(crate) fn to_typed_record<T>(
+    value: &T,
+) -> Result<(Record, Vec<(String, FieldType)>), SerializeError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut record = Record::new();
+    let mut types = Vec::new();
+    value.serialize(FieldSerializer {
+        record: &mut record,
+        types: &mut types,
+        name: String::new(),
+    })?;
+    Ok((record, types))
+}
+
+/// Serializes a value into the field (or fields) of a Record under `name`.
+struct FieldSerializer<'a> {
+    record: &'a mut Record,
+    types: &'a mut Vec<(String, FieldType)>,
+    name: String,
+}
+
+impl<'a> FieldSerializer<'a> {
+    fn emit(
+        self,
+        value: Value,
+        field_type: FieldType,
+    ) -> Result<(), SerializeError> {
+        let name = match self.name.is_empty() {
+            true => String::from("value"),
+            false => self.name,
+        };
+        // Fields of the same name replace each other, as in Record::push
+        match self.types.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, t)) => *t = field_type,
+            None => self.types.push((name.clone(), field_type)),
+        }
+        self.record.push(name, value);
+        Ok(())
+    }
+
+    fn compound(self) -> Compound<'a> {
+        Compound {
+            record: self.record,
+            types: self.types,
+            prefix: self.name,
+            index: 0,
+            key: None,
+        }
+    }
+}
+
+fn child_name(prefix: &str, key: &str) -> String {
+    match prefix.is_empty() {
+        true => key.to_owned(),
+        false => format!("{prefix}.{key}"),
+    }
+}
+
+impl<'a> ser::Serializer for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerializeError> {
+        self.emit(Value::Bool(v), FieldType::Bool)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v), FieldType::Int)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializeError> {
+        self.emit(
+            i64::try_from(v).map_or(Value::Float(v as f64), Value::Int),
+            FieldType::Int,
+        )
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializeError> {
+        self.emit(Value::Int(v.into()), FieldType::Int)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializeError> {
+        self.emit(
+            i64::try_from(v).map_or(Value::Float(v as f64), Value::Int),
+            FieldType::Int,
+        )
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializeError> {
+        self.emit(
+            i64::try_from(v).map_or(Value::Float(v as f64), Value::Int),
+            FieldType::Int,
+        )
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SerializeError> {
+        // Widen through the shortest decimal representation so that e.g.
+        // 0.1f32 is stored as 0.1 rather than 0.10000000149011612.
+        let widened = v.to_string().parse().unwrap_or(v as f64);
+        self.emit(Value::Float(widened), FieldType::Float)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SerializeError> {
+        self.emit(Value::Float(v), FieldType::Float)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializeError> {
+        self.emit(Value::Str(v.to_string()), FieldType::Str)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializeError> {
+        self.emit(Value::Str(v.to_owned()), FieldType::Str)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerializeError> {
+        self.emit(
+            Value::Str(String::from_utf8_lossy(v).to_string()),
+            FieldType::Str,
+        )
+    }
+
+    fn serialize_none(self) -> Result<(), SerializeError> {
+        self.emit(Value::Null, FieldType::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializeError> {
+        self.emit(Value::Null, FieldType::Null)
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.emit(Value::Null, FieldType::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.emit(Value::Str(variant.to_owned()), FieldType::Str)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let name = child_name(&self.name, variant);
+        value.serialize(FieldSerializer {
+            record: self.record,
+            types: self.types,
+            name,
+        })
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Compound<'a>, SerializeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerializeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerializeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerializeError> {
+        let prefix = child_name(&self.name, variant);
+        Ok(FieldSerializer {
+            record: self.record,
+            types: self.types,
+            name: prefix,
+        }
+        .compound())
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Compound<'a>, SerializeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerializeError> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerializeError> {
+        let prefix = child_name(&self.name, variant);
+        Ok(FieldSerializer {
+            record: self.record,
+            types: self.types,
+            name: prefix,
+        }
+        .compound())
+    }
+}
+
+/// Serializes the elements of a container as children of `prefix`.
+struct Compound<'a> {
+    record: &'a mut Record,
+    types: &'a mut Vec<(String, FieldType)>,
+    prefix: String,
+    index: usize,
+    key: Option<String>,
+}
+
+impl<'a> Compound<'a> {
+    fn element<T>(&mut self, key: &str, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(FieldSerializer {
+            record: self.record,
+            types: self.types,
+            name: child_name(&self.prefix, key),
+        })
+    }
+
+    fn next_index<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self.index.to_string();
+        self.index += 1;
+        self.element(&key, value)
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.next_index(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.next_index(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.next_index(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.next_index(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self.key.take().ok_or_else(|| {
+            SerializeError(String::from("map value without a key"))
+        })?;
+        self.element(&key, value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(key, value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(key, value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        Ok(())
+    }
+}
+
+/// Serializes scalar map keys into field names.
+struct KeySerializer;
+
+macro_rules! serialize_key_display {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<String, SerializeError> {
+                Ok(v.to_string())
+            }
+        )*
+    };
+}
+
+macro_rules! unsupported_key {
+    () => {
+        SerializeError(String::from("map keys must be scalars"))
+    };
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<String, SerializeError>;
+    type SerializeTuple = Impossible<String, SerializeError>;
+    type SerializeTupleStruct = Impossible<String, SerializeError>;
+    type SerializeTupleVariant = Impossible<String, SerializeError>;
+    type SerializeMap = Impossible<String, SerializeError>;
+    type SerializeStruct = Impossible<String, SerializeError>;
+    type SerializeStructVariant = Impossible<String, SerializeError>;
+
+    serialize_key_display! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<String, SerializeError> {
+        Ok(String::from_utf8_lossy(v).to_string())
+    }
+
+    fn serialize_none(self) -> Result<String, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<String, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        name: &'static str,
+    ) -> Result<String, SerializeError> {
+        Ok(name.to_owned())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerializeError> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Self::SerializeSeq, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> Result<Self::SerializeTuple, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Self::SerializeMap, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerializeError> {
+        Err(unsupported_key!())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerializeError> {
+        Err(unsupported_key!())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Load {
+        user: f32,
+        system: f32,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+        Line(i8, i8),
+    }
+
+    #[derive(Serialize)]
+    struct Sample {
+        name: &'static str,
+        load: Load,
+        gpu: Option<u8>,
+        frames: Vec<u16>,
+        shape: Shape,
+    }
+
+    fn fields<T>(value: &T) -> Vec<(String, Value)>
+    where
+        T: Serialize,
+    {
+        let record = to_record(value).unwrap();
+        record
+            .iter()
+            .map(|(name, value)| (name.to_owned(), value.clone()))
+            .collect()
+    }
+
+    fn field(name: &str, value: Value) -> (String, Value) {
+        (name.to_owned(), value)
+    }
+
+    #[test]
+    fn nested_fields_are_dotted() {
+        let sample = Sample {
+            name: "tiger",
+            load: Load {
+                user: 0.1,
+                system: 0.25,
+            },
+            gpu: None,
+            frames: vec![16, 17],
+            shape: Shape::Rect {
+                width: 2,
+                height: 3,
+            },
+        };
+
+        assert_eq!(
+            fields(&sample),
+            [
+                field("name", Value::Str("tiger".into())),
+                // f32 values keep their shortest decimal representation
+                field("load.user", Value::Float(0.1)),
+                field("load.system", Value::Float(0.25)),
+                field("gpu", Value::Null),
+                field("frames.0", Value::Int(16)),
+                field("frames.1", Value::Int(17)),
+                field("shape.Rect.width", Value::Int(2)),
+                field("shape.Rect.height", Value::Int(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn options_and_enums_are_flattened() {
+        assert_eq!(fields(&Some(3u8)), [field("value", Value::Int(3))]);
+        assert_eq!(fields(&None::<u8>), [field("value", Value::Null)]);
+        assert_eq!(
+            fields(&Shape::Empty),
+            [field("value", Value::Str("Empty".into()))]
+        );
+        assert_eq!(
+            fields(&Shape::Circle(1.5)),
+            [field("Circle", Value::Float(1.5))]
+        );
+        assert_eq!(
+            fields(&Shape::Line(-1, 2)),
+            [
+                field("Line.0", Value::Int(-1)),
+                field("Line.1", Value::Int(2))
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_are_keyed_by_their_scalar_keys() {
+        let map = BTreeMap::from([(1, true), (2, false)]);
+        assert_eq!(
+            fields(&map),
+            [
+                field("1", Value::Bool(true)),
+                field("2", Value::Bool(false))
+            ]
+        );
+
+        let map = BTreeMap::from([((1, 2), true)]);
+        assert!(to_record(&map).is_err());
+    }
+
+    #[test]
+    fn integers_beyond_i64_become_floats() {
+        assert_eq!(
+            fields(&(i64::MAX as u64)),
+            [field("value", Value::Int(i64::MAX))]
+        );
+        assert_eq!(
+            fields(&u64::MAX),
+            [field("value", Value::Float(u64::MAX as f64))]
+        );
+        assert_eq!(fields(&(-1i128)), [field("value", Value::Int(-1))]);
+    }
+}
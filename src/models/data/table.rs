@@ -0,0 +1,515 @@
+use log::trace;
+use std::io;
+use std::path::Path;
+
+use crate::models::data::serializer;
+use crate::models::{Measurable, Measurements, Record, Value};
+use crate::Result;
+
+/// Named column of dynamically typed values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    name: String,
+    values: Vec<Value>,
+}
+
+impl Column {
+    pub fn new<S>(name: S, values: Vec<Value>) -> Self
+    where
+        S: Into<String>,
+    {
+        Column {
+            name: name.into(),
+            values,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether every non-null value is a number, and at least one exists.
+    pub fn is_numeric(&self) -> bool {
+        let mut numbers = self.values.iter().filter(|v| !v.is_null());
+        let first = numbers.next();
+        first.is_some_and(|v| v.as_f64().is_some())
+            && numbers.all(|v| v.as_f64().is_some())
+    }
+
+    /// Numeric values of this column, skipping nulls and non-numbers.
+    pub fn numeric_values(&self) -> Vec<f64> {
+        self.values.iter().filter_map(Value::as_f64).collect()
+    }
+}
+
+/// Columnar table of untyped measurement data, produced by serializing
+/// Measurables field by field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: usize,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Table {
+            columns: Vec::new(),
+            rows: 0,
+        }
+    }
+
+    /// Tabulates Measurements by serializing each Measurable into a row.
+    pub fn from_measurements<T>(measurements: &Measurements<T>) -> Result<Self>
+    where
+        T: Measurable,
+    {
+        let mut table = Table::new();
+        for measurable in measurements {
+            table.push(&serializer::to_record(measurable)?);
+        }
+        Ok(table)
+    }
+
+    /// Tabulates Measurements like from_measurements, along with the type
+    /// every column was serialized as, rather than the types of its values.
+    /// Columns of fields that are `None` in every row are `Null`.
+    #[cfg(feature = "parquet")]
+    pub(crate) fn from_measurements_typed<T>(
+        measurements: &Measurements<T>,
+    ) -> Result<(Self, Vec<super::FieldType>)>
+    where
+        T: Measurable,
+    {
+        let mut table = Table::new();
+        let mut types: Vec<(String, super::FieldType)> = Vec::new();
+        for measurable in measurements {
+            let (record, fields) = serializer::to_typed_record(measurable)?;
+            for (name, field_type) in fields {
+                match types.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, t)) => *t = t.merge(field_type),
+                    None => types.push((name, field_type)),
+                }
+            }
+            table.push(&record);
+        }
+        // Columns are added in the order their fields first appear
+        let types = types.into_iter().map(|(_, t)| t).collect();
+        Ok((table, types))
+    }
+
+    pub fn from_records<'a, I>(records: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Record>,
+    {
+        let mut table = Table::new();
+        for record in records {
+            table.push(record);
+        }
+        table
+    }
+
+    /// Reads a CSV with a header row, inferring the type of every field.
+    pub fn read_csv<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        trace!("reading table from {path:?}");
+        let mut reader = csv::Reader::from_path(path)?;
+        let mut table = Table::new();
+        for header in reader.headers()? {
+            table.columns.push(Column::new(header, Vec::new()));
+        }
+        for record in reader.deserialize::<Record>() {
+            table.push(&record?);
+        }
+        Ok(table)
+    }
+
+    /// Writes this table as CSV, optionally preceded by a header row.
+    pub fn write_csv<W>(&self, writer: W, headers: bool) -> Result<()>
+    where
+        W: io::Write,
+    {
+        let mut writer = csv::Writer::from_writer(writer);
+        if headers {
+            writer.write_record(self.column_names())?;
+        }
+        for row in 0..self.rows {
+            writer.write_record(
+                self.columns.iter().map(|c| c.values[row].to_string()),
+            )?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Appends a row, adding any new columns. Missing fields are null.
+    pub fn push(&mut self, record: &Record) {
+        for (name, value) in record.iter() {
+            match self.columns.iter_mut().find(|c| c.name == name) {
+                Some(column) => column.values.push(value.clone()),
+                None => {
+                    let mut values = vec![Value::Null; self.rows];
+                    values.push(value.clone());
+                    self.columns.push(Column::new(name, values));
+                }
+            }
+        }
+        self.rows += 1;
+        for column in &mut self.columns {
+            column.values.resize(self.rows, Value::Null);
+        }
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    pub fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|c| c.name.as_str())
+    }
+
+    /// Columns whose values are all numeric.
+    pub fn numeric_columns(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter().filter(|c| c.is_numeric())
+    }
+
+    pub fn row(&self, index: usize) -> Option<Record> {
+        (index < self.rows).then(|| {
+            self.columns
+                .iter()
+                .map(|c| (c.name.clone(), c.values[index].clone()))
+                .collect()
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Record> + '_ {
+        (0..self.rows).filter_map(|i| self.row(i))
+    }
+
+    /// New table with only the named columns, in the given order. Unknown
+    /// names are ignored.
+    pub fn select(&self, names: &[&str]) -> Table {
+        Table {
+            columns: names
+                .iter()
+                .filter_map(|name| self.column(name).cloned())
+                .collect(),
+            rows: self.rows,
+        }
+    }
+
+    /// New table with exactly the named columns, in the given order. Names
+    /// missing from this table become columns of nulls.
+    pub fn conform(&self, names: &[&str]) -> Table {
+        Table {
+            columns: names
+                .iter()
+                .map(|name| match self.column(name) {
+                    Some(column) => column.clone(),
+                    None => Column::new(*name, vec![Value::Null; self.rows]),
+                })
+                .collect(),
+            rows: self.rows,
+        }
+    }
+
+    /// New table with only the rows satisfying @param predicate.
+    pub fn filter<F>(&self, mut predicate: F) -> Table
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        let rows = self.rows().filter(|row| predicate(row)).collect::<Vec<_>>();
+        self.with_rows(&rows)
+    }
+
+    /// Splits the table by the distinct values of column @param name, in
+    /// order of first appearance.
+    pub fn group_by(&self, name: &str) -> Vec<(Value, Table)> {
+        let mut groups: Vec<(Value, Vec<Record>)> = Vec::new();
+        for row in self.rows() {
+            let key = row.get(name).cloned().unwrap_or(Value::Null);
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, rows)) => rows.push(row),
+                None => groups.push((key, vec![row])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(key, rows)| (key, self.with_rows(&rows)))
+            .collect()
+    }
+
+    /// Inner join with @param other on equal values of column @param on.
+    /// Conflicting column names from @param other are suffixed with
+    /// `_right`.
+    pub fn join(&self, other: &Table, on: &str) -> Table {
+        let right_rows = other.rows().collect::<Vec<Record>>();
+        let mut joined = Table::new();
+        for left in self.rows() {
+            let key = match left.get(on) {
+                Some(key) if !key.is_null() => key,
+                _ => continue,
+            };
+            for right in right_rows.iter().filter(|r| r.get(on) == Some(key)) {
+                joined.push(&self.merge(&left, right, on));
+            }
+        }
+        joined
+    }
+
+    /// Joins every row with the row of @param other having the closest
+    /// preceding (or equal) numeric value in column @param on, such as
+    /// aligning monitors polled at different times by `timestamp_ns`.
+    pub fn join_asof(&self, other: &Table, on: &str) -> Table {
+        let mut right_rows = other
+            .rows()
+            .filter_map(|r| r.get(on).and_then(Value::as_f64).map(|k| (k, r)))
+            .collect::<Vec<(f64, Record)>>();
+        right_rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut joined = Table::new();
+        for left in self.rows() {
+            let key = match left.get(on).and_then(Value::as_f64) {
+                Some(key) => key,
+                None => continue,
+            };
+            let preceding = right_rows.partition_point(|(k, _)| *k <= key);
+            if let Some((_, right)) =
+                preceding.checked_sub(1).map(|i| &right_rows[i])
+            {
+                joined.push(&self.merge(&left, right, on));
+            }
+        }
+        joined
+    }
+
+    fn merge(&self, left: &Record, right: &Record, on: &str) -> Record {
+        let mut merged = left.clone();
+        for (name, value) in right.iter().filter(|(name, _)| *name != on) {
+            let name = match left.get(name) {
+                Some(_) => format!("{name}_right"),
+                None => name.to_owned(),
+            };
+            merged.push(name, value.clone());
+        }
+        merged
+    }
+
+    /// New table with this table's columns and the given rows.
+    fn with_rows(&self, rows: &[Record]) -> Table {
+        let mut table = Table {
+            columns: self
+                .columns
+                .iter()
+                .map(|c| Column::new(c.name.clone(), Vec::new()))
+                .collect(),
+            rows: 0,
+        };
+        for row in rows {
+            table.push(row);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::testing::TempDir;
+    use serde::Serialize;
+
+    #[derive(Serialize, Debug)]
+    struct Frame {
+        file: &'static str,
+        time_ns: u64,
+        gpu: Option<f64>,
+    }
+
+    fn frames() -> Table {
+        let mut measurements = Measurements::new();
+        for (file, time_ns, gpu) in [
+            ("tiger", 30, Some(0.5)),
+            ("lion", 10, None),
+            ("tiger", 20, Some(0.7)),
+        ] {
+            measurements.push(Frame { file, time_ns, gpu });
+        }
+        Table::from_measurements(&measurements).unwrap()
+    }
+
+    fn record(fields: &[(&str, Value)]) -> Record {
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn str(s: &str) -> Value {
+        Value::Str(s.to_owned())
+    }
+
+    fn ints(table: &Table, name: &str) -> Vec<Option<i64>> {
+        let column = table.column(name).unwrap();
+        column.values().iter().map(Value::as_i64).collect()
+    }
+
+    #[test]
+    fn push_fills_missing_fields_with_nulls() {
+        let mut table = Table::new();
+        table.push(&record(&[("a", Value::Int(1))]));
+        table.push(&record(&[("b", Value::Int(2))]));
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.column_names().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(ints(&table, "a"), [Some(1), None]);
+        assert_eq!(ints(&table, "b"), [None, Some(2)]);
+        assert_eq!(
+            table.row(1),
+            Some(record(&[("a", Value::Null), ("b", Value::Int(2))]))
+        );
+        assert_eq!(table.row(2), None);
+    }
+
+    #[test]
+    fn numeric_columns_need_one_number() {
+        let table = frames();
+        let numeric: Vec<&str> =
+            table.numeric_columns().map(Column::name).collect();
+
+        // `gpu` has a null, but is numeric otherwise
+        assert_eq!(numeric, ["time_ns", "gpu"]);
+        assert_eq!(table.column("gpu").unwrap().numeric_values(), [0.5, 0.7]);
+        assert!(!Column::new("empty", vec![Value::Null]).is_numeric());
+        assert!(
+            !Column::new("mixed", vec![Value::Int(1), str("a")]).is_numeric()
+        );
+    }
+
+    #[test]
+    fn select_and_filter() {
+        let table = frames();
+
+        let selected = table.select(&["time_ns", "unknown", "file"]);
+        assert_eq!(
+            selected.column_names().collect::<Vec<_>>(),
+            ["time_ns", "file"]
+        );
+
+        let slow = table.filter(|row| {
+            row.get("time_ns").and_then(Value::as_i64) > Some(15)
+        });
+        assert_eq!(ints(&slow, "time_ns"), [Some(30), Some(20)]);
+        assert_eq!(slow.columns().len(), 3);
+        let none = table.filter(|_| false);
+        assert!(none.is_empty());
+        assert_eq!(none.columns().len(), 3);
+    }
+
+    #[test]
+    fn group_by_keeps_the_order_of_first_appearance() {
+        let groups = frames().group_by("file");
+        let keys: Vec<&Value> = groups.iter().map(|(key, _)| key).collect();
+
+        assert_eq!(keys, [&str("tiger"), &str("lion")]);
+        assert_eq!(ints(&groups[0].1, "time_ns"), [Some(30), Some(20)]);
+        assert_eq!(ints(&groups[1].1, "time_ns"), [Some(10)]);
+        // Rows without the column are grouped under null
+        let groups = frames().group_by("unknown");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, Value::Null);
+    }
+
+    #[test]
+    fn join_matches_every_equal_key() {
+        let mut sizes = Table::new();
+        for (file, time_ns) in [("tiger", 1), ("tiger", 2), ("ghost", 3)] {
+            sizes.push(&record(&[
+                ("file", str(file)),
+                ("time_ns", Value::Int(time_ns)),
+            ]));
+        }
+        sizes.push(&record(&[("file", Value::Null)]));
+        let joined = frames().join(&sizes, "file");
+
+        // Conflicting names of the right table are suffixed
+        assert_eq!(
+            joined.column_names().collect::<Vec<_>>(),
+            ["file", "time_ns", "gpu", "time_ns_right"]
+        );
+        assert_eq!(
+            ints(&joined, "time_ns"),
+            [Some(30), Some(30), Some(20), Some(20)]
+        );
+        assert_eq!(
+            ints(&joined, "time_ns_right"),
+            [Some(1), Some(2), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn join_asof_takes_the_closest_preceding_row() {
+        let mut left = Table::new();
+        for t in [5.0, 10.0, 14.9, 30.0] {
+            left.push(&record(&[("t", Value::Float(t))]));
+        }
+        left.push(&record(&[("t", Value::Null)]));
+        let mut right = Table::new();
+        // Unordered, as monitors may be merged from several threads
+        for (t, load) in [(20, 3), (10, 1), (15, 2)] {
+            right.push(&record(&[
+                ("t", Value::Int(t)),
+                ("load", Value::Int(load)),
+            ]));
+        }
+        let joined = left.join_asof(&right, "t");
+
+        // Rows before the first right row, or without a key, are dropped
+        let t: Vec<f64> = joined.column("t").unwrap().numeric_values();
+        assert_eq!(t, [10.0, 14.9, 30.0]);
+        // Equal keys match, and later rows keep the last preceding one
+        assert_eq!(ints(&joined, "load"), [Some(1), Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn csv_round_trips() -> Result<()> {
+        let dir = TempDir::new("table-csv");
+        let path = dir.join("frames.csv");
+        let mut bytes = Vec::new();
+        frames().write_csv(&mut bytes, true)?;
+        std::fs::write(&path, &bytes)?;
+
+        assert_eq!(
+            String::from_utf8_lossy(&bytes),
+            "file,time_ns,gpu\ntiger,30,0.5\nlion,10,\ntiger,20,0.7\n"
+        );
+        assert_eq!(Table::read_csv(&path)?, frames());
+        Ok(())
+    }
+}
@@ -3,10 +3,13 @@ use std::collections::HashMap;
 
 use crate::models::{
     Benchmark, BenchmarkBundle, DriverBuilder, DriverBundle, DriverOptions,
-    DriverWriteMode, Measurable,
+    DriverWriteMode, Environment, Measurable,
 };
+use crate::profilers::NsysProfiler;
+#[cfg(feature = "sqlite")]
+use crate::writers::SqliteDatabase;
 use crate::Result;
-use crate::{log_assert, util};
+use crate::{log_assert, tracers, util};
 
 // Driver fields
 pub struct Driver<T>
@@ -36,7 +39,21 @@ where
 
     pub fn run(self) -> Result<()> {
         let output_dir = self.options.output_dir.clone();
+        // Stages after extract are traced by this Driver's tracer too
+        let tracer = self.options.tracer.clone();
+        let _active = tracer.clone().map(tracers::activate);
+        if let Some(profiler) = self.options.profiler() {
+            if !NsysProfiler::is_profiled() {
+                let report = profiler.profile(&output_dir)?;
+                info!("profile written to {:?}", report.report);
+                return Ok(());
+            }
+        }
         let write_mode = self.options.write_mode().clone();
+        let writers = self.options.writers().to_vec();
+        let plot = self.options.plot().cloned();
+        #[cfg(feature = "sqlite")]
+        let database = self.options.database.clone();
 
         let bundle = self.extract()?;
 
@@ -56,30 +73,54 @@ where
         }
         trace!("landing ready");
 
-        bundle.write(&output_dir)?;
+        bundle.write(&output_dir, writers.as_slice())?;
+
+        if let Some(plot) = plot {
+            tracers::active().mark("plot-stage");
+            trace!("plotting results");
+            if let Err(e) = bundle.plot(output_dir.join("plots"), &plot) {
+                error!("plotting failed: {e}");
+            }
+        }
+
+        #[cfg(feature = "sqlite")]
+        if let Some(database) = database {
+            trace!("storing run in {database:?}");
+            SqliteDatabase::open(database)?.insert_run(&bundle)?;
+        }
+
+        // Tracers active before this Driver are left to their owners
+        if let Some(tracer) = tracer {
+            tracer.flush(&output_dir)?;
+        }
 
         Ok(())
     }
 
     pub fn extract(self) -> Result<DriverBundle<T>> {
+        let environment = Environment::collect();
+        let _tracer = self.options.tracer.clone().map(tracers::activate);
+
         // Create buffers
         let mut bundles: HashMap<String, BenchmarkBundle<T>> = HashMap::new();
+        let mut failures: HashMap<String, String> = HashMap::new();
 
         // Run all benchmarks
-        nvtx::mark("benchmark-stage");
+        tracers::active().mark("benchmark-stage");
         trace!("commencing benchmarks");
         for mut benchmark in self.benchmarks {
-            let benchmark_name = benchmark.metadata().name();
+            let benchmark_name = benchmark.metadata().name().to_owned();
             info!("{benchmark_name}: commencing");
             let benchmark_result = benchmark.run(&self.options);
             match benchmark_result {
                 Ok(bundle) => {
                     info!("{benchmark_name}: completed");
-                    bundles.insert(benchmark_name.to_owned(), bundle);
+                    bundles.insert(benchmark_name, bundle);
                 }
                 Err(e) => {
                     error!("{benchmark_name} failed: {e}");
                     if self.options.on_error_continue {
+                        failures.insert(benchmark_name, e.to_string());
                         trace!("continuing to next benchmark...")
                     } else {
                         panic!("{e}");
@@ -92,6 +133,8 @@ where
         // Package bundle
         let bundle = DriverBundle {
             benchmark_bundles: bundles,
+            benchmark_failures: failures,
+            environment,
         };
         Ok(bundle)
     }
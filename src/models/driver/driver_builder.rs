@@ -1,8 +1,12 @@
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::models::{
-    Benchmark, Driver, DriverOptions, DriverWriteMode, Measurable,
+    Benchmark, Driver, DriverOptions, DriverWriteMode, Measurable, Tracer,
 };
+use crate::plotters::PlotOptions;
+use crate::profilers::NsysProfiler;
+use crate::writers::OutputFormat;
 
 // Driver builder
 pub struct DriverBuilder<T>
@@ -41,6 +45,48 @@ where
         self
     }
 
+    /// Select the output formats results are written in, replacing the
+    /// default of CSV. Several formats may be written at once.
+    pub fn writers<I>(mut self, formats: I) -> Self
+    where
+        I: IntoIterator<Item = OutputFormat>,
+    {
+        self.options.writers = formats.into_iter().collect();
+        self
+    }
+
+    /// Plot the results of every run under `plots/` in the output directory,
+    /// after they have been written.
+    pub fn plot(mut self, options: PlotOptions) -> Self {
+        self.options.plot = Some(options);
+        self
+    }
+
+    /// Send the ranges and marks of the run to @param tracer, which becomes
+    /// the active tracer once the Driver runs.
+    pub fn tracer<R>(mut self, tracer: R) -> Self
+    where
+        R: Tracer + 'static,
+    {
+        self.options.tracer = Some(Arc::new(tracer));
+        self
+    }
+
+    /// Re-launch the run under Nsight Systems with @param profiler, which
+    /// collects its report into the output directory.
+    pub fn profiler(mut self, profiler: NsysProfiler) -> Self {
+        self.options.profiler = Some(profiler);
+        self
+    }
+
+    /// Additionally append every run into the SQLite database at @param
+    /// path, creating it if necessary.
+    #[cfg(feature = "sqlite")]
+    pub fn database(mut self, path: &Path) -> Self {
+        self.options.database = Some(path.to_owned());
+        self
+    }
+
     /// Builder Pattern wrapper for adding new Benchmarks.
     pub fn add(mut self, benchmark: Benchmark<T>) -> Self {
         self.benchmarks.push(benchmark);
@@ -1,4 +1,12 @@
+use anyhow::anyhow;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::models::Tracer;
+use crate::plotters::PlotOptions;
+use crate::profilers::NsysProfiler;
+use crate::writers::OutputFormat;
 
 /// State-Machine Definitions for handling output buffer logic.
 #[derive(Debug, Clone, Copy)]
@@ -22,12 +30,34 @@ impl Default for DriverWriteMode {
     }
 }
 
+impl FromStr for DriverWriteMode {
+    type Err = crate::Error;
+
+    /// Parses a write mode by name: `nomash`, `purge` or `relaxed`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nomash" | "no-mash" => Ok(DriverWriteMode::NoMash),
+            "purge" => Ok(DriverWriteMode::Purge),
+            "relaxed" => Ok(DriverWriteMode::Relaxed),
+            _ => Err(anyhow!(
+                "unknown write mode '{s}', expected nomash, purge or relaxed"
+            )),
+        }
+    }
+}
+
 /// Metadata for handling and storing of output for Benchmarks.
 #[derive(Debug, Clone)]
 pub struct DriverOptions {
     pub(crate) output_dir: PathBuf,
     pub(crate) write_mode: DriverWriteMode,
     pub(crate) on_error_continue: bool,
+    pub(crate) writers: Vec<OutputFormat>,
+    pub(crate) plot: Option<PlotOptions>,
+    pub(crate) tracer: Option<Arc<dyn Tracer>>,
+    pub(crate) profiler: Option<NsysProfiler>,
+    #[cfg(feature = "sqlite")]
+    pub(crate) database: Option<PathBuf>,
 }
 
 impl Default for DriverOptions {
@@ -36,6 +66,11 @@ impl Default for DriverOptions {
     /// + output_dir_name: 'output'
     /// + write_mode: DriverWriteMode::Relaxed
     /// + on_error_continue: false
+    /// + writers: [OutputFormat::Csv]
+    /// + plot: None
+    /// + tracer: None
+    /// + profiler: None
+    /// + database: None
     fn default() -> Self {
         DriverOptions::new("output", DriverWriteMode::default(), false)
     }
@@ -52,6 +87,12 @@ impl DriverOptions {
             output_dir,
             write_mode,
             on_error_continue,
+            writers: vec![OutputFormat::default()],
+            plot: None,
+            tracer: None,
+            profiler: None,
+            #[cfg(feature = "sqlite")]
+            database: None,
         }
     }
 }
@@ -66,4 +107,20 @@ impl DriverOptions {
     pub fn on_error_continue(&self) -> &bool {
         &self.on_error_continue
     }
+    pub fn writers(&self) -> &[OutputFormat] {
+        &self.writers
+    }
+    pub fn plot(&self) -> Option<&PlotOptions> {
+        self.plot.as_ref()
+    }
+    pub fn tracer(&self) -> Option<&Arc<dyn Tracer>> {
+        self.tracer.as_ref()
+    }
+    pub fn profiler(&self) -> Option<&NsysProfiler> {
+        self.profiler.as_ref()
+    }
+    #[cfg(feature = "sqlite")]
+    pub fn database(&self) -> Option<&Path> {
+        self.database.as_deref()
+    }
 }
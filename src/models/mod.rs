@@ -10,6 +10,9 @@ pub use monitor::*;
 mod plotter;
 pub use plotter::*;
 
+mod tracer;
+pub use tracer::*;
+
 mod writer;
 pub use writer::*;
 
@@ -1,3 +1,5 @@
+use anyhow::anyhow;
+use std::str::FromStr;
 use std::time::Duration;
 
 /// Type for defining how many times a monitor should execute and it's equivalent duration.
@@ -20,3 +22,42 @@ impl MonitorFrequency {
         }
     }
 }
+
+impl FromStr for MonitorFrequency {
+    type Err = crate::Error;
+
+    /// Parses a positive frequency in Hertz (`10hz`) or period in
+    /// milliseconds (`500ms`) or seconds (`2s`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let (value, unit) = match lower.strip_suffix("hz") {
+            Some(hz) => (hz, "hz"),
+            None => match lower.strip_suffix("ms") {
+                Some(ms) => (ms, "ms"),
+                None => (lower.strip_suffix('s').unwrap_or("-"), "s"),
+            },
+        };
+        let value = value.trim().parse::<f64>().unwrap_or(f64::NAN);
+        if !(value.is_finite() && value > 0.0) {
+            return Err(anyhow!(
+                "invalid frequency '{s}', expected e.g. 10hz, 500ms or 2s"
+            ));
+        }
+        let frequency = match unit {
+            "hz" if value.fract() == 0.0 => {
+                MonitorFrequency::Hertz(value as usize)
+            }
+            "hz" => {
+                MonitorFrequency::Duration(Duration::from_secs_f64(1.0 / value))
+            }
+            "ms" => {
+                MonitorFrequency::Duration(Duration::from_secs_f64(value / 1e3))
+            }
+            _ => MonitorFrequency::Duration(Duration::from_secs_f64(value)),
+        };
+        match frequency.as_duration().is_zero() {
+            true => Err(anyhow!("frequency '{s}' is too high to poll")),
+            false => Ok(frequency),
+        }
+    }
+}
@@ -1,31 +1,74 @@
-use plotters::prelude::ChartContext;
+use log::trace;
+use plotters::coord::Shift;
+use plotters::prelude::{
+    BitMapBackend, DrawingArea, DrawingBackend, IntoDrawingArea, SVGBackend,
+    WHITE,
+};
 use std::path::Path;
 
-use crate::models::{Measurable, Measurements};
+use crate::models::Table;
+use crate::util;
 use crate::Result;
 
+/// Renders a chart of untyped measurement data onto any plotters backend.
 pub trait Plotter {
-    fn plot<T, DB, CT>(
+    /// Draws @param data onto @param area, which has already been cleared.
+    fn plot<DB>(
         &self,
-        data: &Measurements<T>,
-    ) -> Result<ChartContext<DB, CT>>
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
     where
-        T: Measurable,
-        DB: plotters::prelude::DrawingBackend,
-        CT: plotters::prelude::CoordTranslate;
+        DB: DrawingBackend,
+        DB::ErrorType: 'static;
 
-    fn save_plot<P, T>(&self, _data: &Measurements<T>, _path: P) -> Result<()>
+    /// Width and height in pixels of saved plots.
+    fn size(&self) -> (u32, u32) {
+        (1024, 768)
+    }
+
+    /// Saves a plot of @param data to @param path. A `png` extension renders
+    /// a bitmap, anything else renders an SVG.
+    fn save_plot<P>(&self, data: &Table, path: P) -> Result<()>
     where
         P: AsRef<Path>,
-        T: Measurable,
     {
-        todo!("Save plot");
+        let mut path = path.as_ref().to_owned();
+        let is_png = path.extension().is_some_and(|ext| ext == "png");
+
+        trace!("saving plot to {path:?}");
+        if let Some(parent) = path.parent() {
+            util::io::dir_create_all(parent)?;
+        }
+
+        if is_png {
+            let area =
+                BitMapBackend::new(&path, self.size()).into_drawing_area();
+            area.fill(&WHITE)?;
+            self.plot(data, &area)?;
+            area.present()?;
+        } else {
+            path.set_extension("svg");
+            let area = SVGBackend::new(&path, self.size()).into_drawing_area();
+            area.fill(&WHITE)?;
+            self.plot(data, &area)?;
+            area.present()?;
+        }
+
+        Ok(())
     }
 
-    fn show_plot<T>(&self, _data: &Measurements<T>) -> Result<()>
-    where
-        T: Measurable,
-    {
-        todo!("Show plot");
+    /// Renders a plot of @param data as an SVG document, such as for
+    /// embedding in HTML.
+    fn plot_svg(&self, data: &Table) -> Result<String> {
+        let mut svg = String::new();
+        {
+            let area = SVGBackend::with_string(&mut svg, self.size())
+                .into_drawing_area();
+            area.fill(&WHITE)?;
+            self.plot(data, &area)?;
+            area.present()?;
+        }
+        Ok(svg)
     }
 }
@@ -0,0 +1,2 @@
+mod tracer;
+pub use tracer::Tracer;
@@ -0,0 +1,24 @@
+use std::fmt::Debug;
+use std::path::Path;
+
+use crate::Result;
+
+/// Receives the ranges and marks of a run, such as `benching {name}` around
+/// each BenchmarkFn, e.g. to annotate the timeline of a profiler.
+pub trait Tracer: Debug + Send + Sync {
+    /// Open a range named @param name on the calling thread, nested in any
+    /// range it has open.
+    fn range_push(&self, name: &str);
+
+    /// Close the innermost range open on the calling thread.
+    fn range_pop(&self);
+
+    /// Mark an instant named @param name.
+    fn mark(&self, name: &str);
+
+    /// Write anything recorded so far to @param output_dir, once a Driver
+    /// has written its results there.
+    fn flush(&self, _output_dir: &Path) -> Result<()> {
+        Ok(())
+    }
+}
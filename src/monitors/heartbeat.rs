@@ -16,6 +16,7 @@ unsafe impl Sync for HeartbeatMeasurement {}
 pub struct HeartbeatMonitor {
     beating: bool,
     beating_since: Option<Instant>,
+    frequency: MonitorFrequency,
 }
 unsafe impl Send for HeartbeatMonitor {}
 
@@ -24,6 +25,15 @@ impl HeartbeatMonitor {
         HeartbeatMonitor {
             beating: false,
             beating_since: None,
+            frequency: MonitorFrequency::Hertz(2),
+        }
+    }
+
+    /// Create a HeartbeatMonitor beating at @param frequency instead of 2Hz.
+    pub fn with_frequency(frequency: MonitorFrequency) -> Self {
+        HeartbeatMonitor {
+            frequency,
+            ..HeartbeatMonitor::default()
         }
     }
 }
@@ -32,6 +42,7 @@ impl Default for HeartbeatMonitor {
         Self {
             beating: false,
             beating_since: None,
+            frequency: MonitorFrequency::Hertz(2),
         }
     }
 }
@@ -42,7 +53,7 @@ impl Monitor for HeartbeatMonitor {
     }
 
     fn frequency(&self) -> MonitorFrequency {
-        MonitorFrequency::Hertz(2)
+        self.frequency
     }
 
     fn on_start(&mut self) {
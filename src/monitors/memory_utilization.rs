@@ -1,40 +1,58 @@
-use anyhow::Result;
+use serde::Serialize;
 use systemstat::{Platform, System};
 
-use crate::models::{Measurable, Monitor, MonitorFrequency, MonitorMetadata};
-use crate::monitors::cpu_utilization::MonitorFrequency::Hertz;
-use std::{thread, time::Duration};
+use crate::models::{Measurement, Monitor, MonitorFrequency};
+use crate::Result;
+
+/// Type for system memory metrics, in bytes.
+#[derive(Serialize, Debug)]
+struct MemoryMeasurement {
+    total: u64,
+    free: u64,
+    used: u64,
+    utilization: f64,
+}
+unsafe impl Send for MemoryMeasurement {}
+unsafe impl Sync for MemoryMeasurement {}
 
 pub struct MemoryUtilizationMonitor {
-    metadata: MonitorMetadata,
+    pub name: &'static str,
+    pub frequency: MonitorFrequency,
 }
 unsafe impl Send for MemoryUtilizationMonitor {}
 
 impl Default for MemoryUtilizationMonitor {
+    /// Generates a MemoryUtilizationMonitor type with:
+    /// + name: "Memory Utilization"
+    /// + frequency: 10Hz
     fn default() -> Self {
         Self {
-            metadata: MemoryUtilizationMonitor {
-                name: String::from("Memory Utilization"),
-                frequency: Hertz(100),
-            },
+            name: "Memory Utilization",
+            frequency: MonitorFrequency::Hertz(10),
         }
     }
 }
 
 impl Monitor for MemoryUtilizationMonitor {
-    fn metadata(&self) -> &MonitorMetadata {
-        &self.metadata
+    fn name(&self) -> &'static str {
+        self.name
     }
 
-    fn on_start(&mut self) {}
-
-    fn poll(&self) -> Result<Measurable> {
-        let sys = System::new();
-        let memory = sys.memory()?;
-        let utilization =
-            (mem.free.as_u64() as f64 / mem.total.as_u64() as f64);
-        Ok(Measurable::Float(utilization))
+    fn frequency(&self) -> MonitorFrequency {
+        self.frequency
     }
 
-    fn on_stop(&mut self) {}
+    fn poll(&self) -> Result<Measurement> {
+        let memory = System::new().memory()?;
+        let total = memory.total.as_u64();
+        let free = memory.free.as_u64();
+        let used = total.saturating_sub(free);
+        let memory_measurement = MemoryMeasurement {
+            total,
+            free,
+            used,
+            utilization: used as f64 / total.max(1) as f64,
+        };
+        Ok(Measurement::from(memory_measurement))
+    }
 }
@@ -6,3 +6,6 @@ pub use heartbeat::HeartbeatMonitor;
 
 mod cpu_utilization;
 pub use cpu_utilization::CpuUtilizationMonitor;
+
+mod memory_utilization;
+pub use memory_utilization::MemoryUtilizationMonitor;
@@ -0,0 +1,106 @@
+use plotters::coord::Shift;
+use plotters::prelude::{
+    Circle, Color, DrawingArea, DrawingBackend, Palette, Palette99,
+    PathElement, Rectangle,
+};
+
+use crate::analysis::statistics;
+use crate::models::{Plotter, Table};
+use crate::Result;
+
+/// Box plot of one or more numeric columns: quartiles, median, whiskers up to
+/// 1.5 times the interquartile range, and outliers beyond them.
+#[derive(Debug, Clone, Default)]
+pub struct BoxPlotter {
+    columns: Vec<String>,
+    title: Option<String>,
+}
+
+impl BoxPlotter {
+    pub fn new() -> Self {
+        BoxPlotter::default()
+    }
+
+    /// Columns to plot, replacing the default of every numeric column.
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl Plotter for BoxPlotter {
+    fn plot<DB>(
+        &self,
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        let columns = super::numeric_columns(data, &self.columns)?;
+        let mut chart =
+            super::category_chart(area, self.title.as_deref(), &columns)?;
+
+        for (i, column) in columns.iter().enumerate() {
+            let values = column.numeric_values();
+            if values.is_empty() {
+                continue;
+            }
+            let color = Palette99::pick(i).to_rgba();
+            let x = i as f64;
+            let q1 = statistics::percentile(&values, 25.0);
+            let q2 = statistics::median(&values);
+            let q3 = statistics::percentile(&values, 75.0);
+            let fence = 1.5 * (q3 - q1);
+            let inliers = values
+                .iter()
+                .copied()
+                .filter(|v| (q1 - fence..=q3 + fence).contains(v));
+            let (low, high) = inliers
+                .fold((q1, q3), |(low, high), v| (low.min(v), high.max(v)));
+
+            chart.draw_series([
+                Rectangle::new(
+                    [(x - 0.3, q1), (x + 0.3, q3)],
+                    color.mix(0.3).filled(),
+                ),
+                Rectangle::new(
+                    [(x - 0.3, q1), (x + 0.3, q3)],
+                    color.stroke_width(2),
+                ),
+            ])?;
+            chart.draw_series(
+                [
+                    vec![(x - 0.3, q2), (x + 0.3, q2)],
+                    vec![(x, q3), (x, high)],
+                    vec![(x, q1), (x, low)],
+                    vec![(x - 0.15, high), (x + 0.15, high)],
+                    vec![(x - 0.15, low), (x + 0.15, low)],
+                ]
+                .into_iter()
+                .map(|line| PathElement::new(line, color.stroke_width(2))),
+            )?;
+            chart.draw_series(
+                values
+                    .iter()
+                    .filter(|v| !(low..=high).contains(*v))
+                    .map(|v| Circle::new((x, *v), 3, color)),
+            )?;
+        }
+
+        Ok(())
+    }
+}
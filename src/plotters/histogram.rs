@@ -0,0 +1,102 @@
+use anyhow::ensure;
+use plotters::coord::Shift;
+use plotters::prelude::{
+    ChartBuilder, Color, DrawingArea, DrawingBackend, Rectangle, BLUE,
+};
+
+use crate::models::{Plotter, Table};
+use crate::Result;
+
+/// Histogram of the values of a single numeric column.
+#[derive(Debug, Clone)]
+pub struct HistogramPlotter {
+    column: String,
+    bins: usize,
+    title: Option<String>,
+}
+
+impl HistogramPlotter {
+    /// Generates a HistogramPlotter of @param column with 20 bins.
+    pub fn new<S>(column: S) -> Self
+    where
+        S: Into<String>,
+    {
+        HistogramPlotter {
+            column: column.into(),
+            bins: 20,
+            title: None,
+        }
+    }
+
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = bins.max(1);
+        self
+    }
+
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl Plotter for HistogramPlotter {
+    fn plot<DB>(
+        &self,
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        let column =
+            super::numeric_columns(data, std::slice::from_ref(&self.column))?
+                [0];
+        // NaN or infinite values cannot be binned
+        let values: Vec<f64> = column
+            .numeric_values()
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .collect();
+        ensure!(!values.is_empty(), "no values of '{}' to plot", self.column);
+
+        let range = super::padded_range(values.iter().copied());
+        let width = (range.end - range.start) / self.bins as f64;
+        let mut counts = vec![0usize; self.bins];
+        for value in &values {
+            let bin = ((value - range.start) / width) as usize;
+            counts[bin.min(self.bins - 1)] += 1;
+        }
+        let max_count = counts.iter().copied().max().unwrap_or(0);
+
+        let mut builder = ChartBuilder::on(area);
+        builder
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60);
+        if let Some(title) = &self.title {
+            builder.caption(title, ("sans-serif", 24));
+        }
+        let mut chart = builder
+            .build_cartesian_2d(range.clone(), 0.0..max_count as f64 * 1.1)?;
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_desc(column.name())
+            .y_desc("count")
+            .draw()?;
+
+        chart.draw_series(counts.iter().enumerate().map(|(i, count)| {
+            let start = range.start + i as f64 * width;
+            Rectangle::new(
+                [(start, 0.0), (start + width, *count as f64)],
+                BLUE.mix(0.6).filled(),
+            )
+        }))?;
+
+        Ok(())
+    }
+}
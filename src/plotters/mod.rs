@@ -0,0 +1,203 @@
+//! Plotters charting untyped measurement data, saved as SVG or PNG through
+//! the `Plotter` trait.
+
+use anyhow::{anyhow, ensure};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::Shift;
+use plotters::prelude::{
+    Cartesian2d, ChartBuilder, ChartContext, DrawingArea, DrawingBackend,
+};
+use std::ops::Range;
+
+use crate::models::{Column, Table};
+use crate::Result;
+
+mod time_series;
+pub use time_series::TimeSeriesPlotter;
+
+mod histogram;
+pub use histogram::HistogramPlotter;
+
+mod box_plot;
+pub use box_plot::BoxPlotter;
+
+mod violin;
+pub use violin::ViolinPlotter;
+
+mod scaling;
+pub use scaling::ScalingPlotter;
+
+mod plot_options;
+pub use plot_options::PlotFormat;
+pub use plot_options::PlotOptions;
+
+type Chart<'a, DB> =
+    ChartContext<'a, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>;
+
+/// The named numeric columns of @param data, or all of them if @param names
+/// is empty.
+fn numeric_columns<'a>(
+    data: &'a Table,
+    names: &[String],
+) -> Result<Vec<&'a Column>> {
+    let columns = match names.is_empty() {
+        true => data.numeric_columns().collect::<Vec<&Column>>(),
+        false => names
+            .iter()
+            .map(|name| {
+                data.column(name).filter(|c| c.is_numeric()).ok_or_else(|| {
+                    anyhow!("no numeric column '{name}' to plot")
+                })
+            })
+            .collect::<Result<Vec<&Column>>>()?,
+    };
+    ensure!(!columns.is_empty(), "no numeric columns to plot");
+    Ok(columns)
+}
+
+/// Range covering @param values with a margin of 5% either side. A single
+/// distinct value is widened so that it still has an extent.
+fn padded_range<I>(values: I) -> Range<f64>
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (min, max) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if min > max {
+        return 0.0..1.0;
+    }
+    let padding = match max - min {
+        extent if extent > 0.0 => extent * 0.05,
+        _ => (min.abs() * 0.05).max(0.5),
+    };
+    (min - padding)..(max + padding)
+}
+
+/// Chart with one slot per column along the x-axis, labelled by name, for
+/// comparing the distributions of several columns.
+fn category_chart<'a, DB>(
+    area: &'a DrawingArea<DB, Shift>,
+    title: Option<&str>,
+    columns: &[&Column],
+) -> Result<Chart<'a, DB>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let y_range = padded_range(columns.iter().flat_map(|c| c.numeric_values()));
+    let x_range = -0.5..(columns.len() as f64 - 0.5);
+
+    let mut builder = ChartBuilder::on(area);
+    builder
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(80);
+    if let Some(title) = title {
+        builder.caption(title, ("sans-serif", 24));
+    }
+    let mut chart = builder.build_cartesian_2d(x_range, y_range)?;
+
+    let label = |x: &f64| match (x.fract() == 0.0, columns.get(*x as usize)) {
+        (true, Some(column)) => column.name().to_owned(),
+        _ => String::new(),
+    };
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(columns.len())
+        .x_label_formatter(&label)
+        .draw()?;
+
+    Ok(chart)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::models::{Measurements, Plotter};
+    use crate::util::testing::TempDir;
+
+    #[derive(Serialize, Debug)]
+    struct Sample {
+        timestamp_ns: u64,
+        primitives: u32,
+        time_ns: f64,
+        fps: f64,
+    }
+
+    fn samples() -> Table {
+        let mut measurements = Measurements::new();
+        for i in 0..40u32 {
+            measurements.push(Sample {
+                timestamp_ns: u64::from(i) * 1_000_000,
+                primitives: 10 * (i % 4 + 1),
+                time_ns: f64::from(i % 7) * 100.0 + 1000.0,
+                fps: 60.0 - f64::from(i % 5),
+            });
+        }
+        Table::from_measurements(&measurements).unwrap()
+    }
+
+    fn assert_svg<P>(plotter: P, title: &str)
+    where
+        P: Plotter,
+    {
+        let svg = plotter.plot_svg(&samples()).unwrap();
+        assert!(svg.starts_with("<svg"), "{svg}");
+        assert!(svg.contains(title), "{title} missing from the plot");
+    }
+
+    #[test]
+    fn plotters_render_svg() {
+        assert_svg(
+            TimeSeriesPlotter::new()
+                .series(["fps"])
+                .marker("start", 5e6)
+                .title("series"),
+            "series",
+        );
+        assert_svg(
+            HistogramPlotter::new("time_ns").bins(5).title("histogram"),
+            "histogram",
+        );
+        assert_svg(
+            BoxPlotter::new().columns(["time_ns", "fps"]).title("box"),
+            "box",
+        );
+        assert_svg(ViolinPlotter::new().title("violin"), "violin");
+        assert_svg(
+            ScalingPlotter::new("primitives", "time_ns").title("scaling"),
+            "scaling",
+        );
+    }
+
+    #[test]
+    fn plotters_reject_missing_columns() {
+        let data = samples();
+        assert!(HistogramPlotter::new("missing").plot_svg(&data).is_err());
+        assert!(BoxPlotter::new()
+            .columns(["missing"])
+            .plot_svg(&data)
+            .is_err());
+        assert!(ScalingPlotter::new("primitives", "missing")
+            .plot_svg(&data)
+            .is_err());
+        assert!(TimeSeriesPlotter::new().plot_svg(&Table::new()).is_err());
+    }
+
+    #[test]
+    fn save_plot_defaults_to_svg() -> Result<()> {
+        let dir = TempDir::new("plotters");
+        BoxPlotter::new().save_plot(&samples(), dir.join("box.plot"))?;
+
+        let svg = std::fs::read_to_string(dir.join("box.svg"))?;
+        assert!(svg.starts_with("<svg"));
+        Ok(())
+    }
+}
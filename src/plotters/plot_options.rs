@@ -0,0 +1,80 @@
+use anyhow::anyhow;
+use std::str::FromStr;
+
+/// Selectable image format of saved plots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotFormat {
+    Svg,
+    Png,
+}
+
+impl Default for PlotFormat {
+    /// Generates a Default State of Svg.
+    fn default() -> Self {
+        PlotFormat::Svg
+    }
+}
+
+impl PlotFormat {
+    /// File extension of this format, which selects the plotting backend.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PlotFormat::Svg => "svg",
+            PlotFormat::Png => "png",
+        }
+    }
+}
+
+impl FromStr for PlotFormat {
+    type Err = crate::Error;
+
+    /// Parses a format by its file extension, `svg` or `png`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "svg" => Ok(PlotFormat::Svg),
+            "png" => Ok(PlotFormat::Png),
+            _ => Err(anyhow!("unknown plot format '{s}', expected svg or png")),
+        }
+    }
+}
+
+/// Configuration of the plot stage a Driver runs after writing results.
+#[derive(Debug, Clone)]
+pub struct PlotOptions {
+    pub(crate) formats: Vec<PlotFormat>,
+    pub(crate) comparison: bool,
+}
+
+impl Default for PlotOptions {
+    /// Generates a PlotOptions type with:
+    /// + formats: [PlotFormat::Svg]
+    /// + comparison: true
+    fn default() -> Self {
+        PlotOptions {
+            formats: vec![PlotFormat::default()],
+            comparison: true,
+        }
+    }
+}
+
+impl PlotOptions {
+    pub fn new() -> Self {
+        PlotOptions::default()
+    }
+
+    /// Select the image formats plots are saved in, replacing the default of
+    /// SVG. Several formats may be saved at once.
+    pub fn formats<I>(mut self, formats: I) -> Self
+    where
+        I: IntoIterator<Item = PlotFormat>,
+    {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// Toggle the charts comparing each measurement field across benchmarks.
+    pub fn comparison(mut self, comparison: bool) -> Self {
+        self.comparison = comparison;
+        self
+    }
+}
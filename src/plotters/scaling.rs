@@ -0,0 +1,155 @@
+use anyhow::ensure;
+use plotters::coord::Shift;
+use plotters::prelude::{
+    ChartBuilder, Circle, Color, DrawingArea, DrawingBackend, ErrorBar,
+    LineSeries, Palette, Palette99, PathElement, BLACK, WHITE,
+};
+
+use crate::analysis::statistics;
+use crate::models::{Plotter, Table};
+use crate::Result;
+
+/// Line plot of the median of one column for each distinct value of another,
+/// with error bars spanning the interquartile range. Suited to how a metric
+/// scales with a parameter, such as render time against primitive count.
+#[derive(Debug, Clone)]
+pub struct ScalingPlotter {
+    x: String,
+    y: String,
+    series: Option<String>,
+    title: Option<String>,
+}
+
+impl ScalingPlotter {
+    /// Generates a ScalingPlotter of column @param y over column @param x.
+    pub fn new<X, Y>(x: X, y: Y) -> Self
+    where
+        X: Into<String>,
+        Y: Into<String>,
+    {
+        ScalingPlotter {
+            x: x.into(),
+            y: y.into(),
+            series: None,
+            title: None,
+        }
+    }
+
+    /// Draw a separate, labelled line for each distinct value of
+    /// @param column, such as the benchmarks of different primitives.
+    pub fn series<S>(mut self, column: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.series = Some(column.into());
+        self
+    }
+
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// (x, q1, median, q3) of every group of @param data sharing a value of
+    /// the x column, in ascending order of x.
+    fn quartiles(&self, data: &Table) -> Vec<(f64, f64, f64, f64)> {
+        let mut groups = data
+            .group_by(&self.x)
+            .into_iter()
+            .filter_map(|(x, group)| {
+                let values = group.column(&self.y)?.numeric_values();
+                match (x.as_f64(), values.is_empty()) {
+                    (Some(x), false) => Some((
+                        x,
+                        statistics::percentile(&values, 25.0),
+                        statistics::median(&values),
+                        statistics::percentile(&values, 75.0),
+                    )),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<(f64, f64, f64, f64)>>();
+        groups.sort_by(|a, b| a.0.total_cmp(&b.0));
+        groups
+    }
+}
+
+impl Plotter for ScalingPlotter {
+    fn plot<DB>(
+        &self,
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        super::numeric_columns(data, &[self.x.clone(), self.y.clone()])?;
+
+        let series = match &self.series {
+            Some(column) => data
+                .group_by(column)
+                .into_iter()
+                .map(|(label, group)| (label.to_string(), group))
+                .collect(),
+            None => vec![(String::new(), data.clone())],
+        }
+        .into_iter()
+        .map(|(label, group)| (label, self.quartiles(&group)))
+        .filter(|(_, groups)| !groups.is_empty())
+        .collect::<Vec<(String, Vec<(f64, f64, f64, f64)>)>>();
+        ensure!(!series.is_empty(), "no values of '{}' to plot", self.y);
+
+        let groups = series.iter().flat_map(|(_, groups)| groups);
+        let x_range = super::padded_range(groups.clone().map(|g| g.0));
+        let y_range = super::padded_range(groups.flat_map(|g| [g.1, g.3]));
+
+        let mut builder = ChartBuilder::on(area);
+        builder
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(80);
+        if let Some(title) = &self.title {
+            builder.caption(title, ("sans-serif", 24));
+        }
+        let mut chart = builder.build_cartesian_2d(x_range, y_range)?;
+        chart
+            .configure_mesh()
+            .x_desc(self.x.as_str())
+            .y_desc(self.y.as_str())
+            .draw()?;
+
+        for (i, (label, groups)) in series.iter().enumerate() {
+            let color = Palette99::pick(i).to_rgba();
+            let line = chart.draw_series(LineSeries::new(
+                groups.iter().map(|g| (g.0, g.2)),
+                color.stroke_width(2),
+            ))?;
+            if self.series.is_some() {
+                line.label(label).legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], color)
+                });
+            }
+            chart.draw_series(groups.iter().map(|g| {
+                ErrorBar::new_vertical(g.0, g.1, g.2, g.3, color.filled(), 10)
+            }))?;
+            chart.draw_series(
+                groups
+                    .iter()
+                    .map(|g| Circle::new((g.0, g.2), 4, color.filled())),
+            )?;
+        }
+        if self.series.is_some() {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+
+        Ok(())
+    }
+}
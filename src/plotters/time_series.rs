@@ -0,0 +1,159 @@
+use anyhow::{anyhow, ensure};
+use plotters::coord::Shift;
+use plotters::prelude::{
+    ChartBuilder, Color, DrawingArea, DrawingBackend, LineSeries, Palette,
+    Palette99, PathElement, Text, BLACK, WHITE,
+};
+
+use crate::models::{Plotter, Table};
+use crate::Result;
+
+/// Line plot of one or more columns over a time (or any numeric) column, such
+/// as the samples of a monitor.
+#[derive(Debug, Clone)]
+pub struct TimeSeriesPlotter {
+    x: String,
+    series: Vec<String>,
+    markers: Vec<(String, f64)>,
+    title: Option<String>,
+}
+
+impl Default for TimeSeriesPlotter {
+    /// Generates a TimeSeriesPlotter of every numeric column over the
+    /// `timestamp_ns` of monitor samples.
+    fn default() -> Self {
+        TimeSeriesPlotter {
+            x: String::from("timestamp_ns"),
+            series: Vec::new(),
+            markers: Vec::new(),
+            title: None,
+        }
+    }
+}
+
+impl TimeSeriesPlotter {
+    pub fn new() -> Self {
+        TimeSeriesPlotter::default()
+    }
+
+    /// Column plotted along the x-axis. Columns ending in `_ns` are shown in
+    /// seconds.
+    pub fn x<S>(mut self, column: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.x = column.into();
+        self
+    }
+
+    /// Columns plotted as lines, replacing the default of every numeric
+    /// column.
+    pub fn series<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.series = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Draw a labelled vertical line at @param x, in units of the x column,
+    /// such as the start and end of a benchmark.
+    pub fn marker<S>(mut self, label: S, x: f64) -> Self
+    where
+        S: Into<String>,
+    {
+        self.markers.push((label.into(), x));
+        self
+    }
+
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl Plotter for TimeSeriesPlotter {
+    fn plot<DB>(
+        &self,
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        let x = data
+            .column(&self.x)
+            .filter(|c| c.is_numeric())
+            .ok_or_else(|| anyhow!("no numeric column '{}' to plot", self.x))?;
+        let series = super::numeric_columns(data, &self.series)?
+            .into_iter()
+            .filter(|c| c.name() != self.x)
+            .collect::<Vec<_>>();
+        ensure!(!series.is_empty(), "no numeric series to plot");
+
+        let (scale, x_desc) = match self.x.ends_with("_ns") {
+            true => (1e-9, String::from("time (s)")),
+            false => (1.0, self.x.clone()),
+        };
+        let xs = x
+            .values()
+            .iter()
+            .map(|v| v.as_f64().map(|x| x * scale))
+            .collect::<Vec<Option<f64>>>();
+        let markers = self.markers.iter().map(|(_, x)| x * scale);
+        let x_range =
+            super::padded_range(xs.iter().flatten().copied().chain(markers));
+        let y_range =
+            super::padded_range(series.iter().flat_map(|c| c.numeric_values()));
+
+        let mut builder = ChartBuilder::on(area);
+        builder
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(80);
+        if let Some(title) = &self.title {
+            builder.caption(title, ("sans-serif", 24));
+        }
+        let mut chart = builder.build_cartesian_2d(x_range, y_range.clone())?;
+        chart.configure_mesh().x_desc(x_desc).draw()?;
+
+        for (i, column) in series.iter().enumerate() {
+            let color = Palette99::pick(i).to_rgba();
+            let points = xs
+                .iter()
+                .zip(column.values())
+                .filter_map(|(x, y)| Some(((*x)?, y.as_f64()?)));
+            chart
+                .draw_series(LineSeries::new(points, color.stroke_width(2)))?
+                .label(column.name())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], color)
+                });
+        }
+        let (y_min, y_max) = (y_range.start, y_range.end);
+        for (label, x) in &self.markers {
+            let x = x * scale;
+            chart.draw_series([PathElement::new(
+                vec![(x, y_min), (x, y_max)],
+                BLACK.stroke_width(1),
+            )])?;
+            chart.draw_series([Text::new(
+                label.clone(),
+                (x, y_max),
+                ("sans-serif", 14),
+            )])?;
+        }
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        Ok(())
+    }
+}
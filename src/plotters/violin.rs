@@ -0,0 +1,125 @@
+use plotters::coord::Shift;
+use plotters::prelude::{
+    Circle, Color, DrawingArea, DrawingBackend, Palette, Palette99,
+    PathElement, Polygon, BLACK, WHITE,
+};
+
+use crate::analysis::statistics;
+use crate::models::{Plotter, Table};
+use crate::Result;
+
+/// Number of points the density of each violin is evaluated at.
+const RESOLUTION: usize = 64;
+
+/// Violin plot of one or more numeric columns: a mirrored kernel density
+/// estimate of each, with the interquartile range and median drawn inside.
+#[derive(Debug, Clone, Default)]
+pub struct ViolinPlotter {
+    columns: Vec<String>,
+    title: Option<String>,
+}
+
+impl ViolinPlotter {
+    pub fn new() -> Self {
+        ViolinPlotter::default()
+    }
+
+    /// Columns to plot, replacing the default of every numeric column.
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn title<S>(mut self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Gaussian kernel density estimate of @param values at evenly spaced points
+/// across their range, using Silverman's rule of thumb for the bandwidth.
+fn density(values: &[f64]) -> Vec<(f64, f64)> {
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+    let n = values.len() as f64;
+    let bandwidth = 1.06 * statistics::std_dev(values) * n.powf(-0.2);
+    if bandwidth.is_nan() || bandwidth <= 0.0 || max <= min {
+        return vec![(min, 1.0), (max, 1.0)];
+    }
+    (0..RESOLUTION)
+        .map(|i| min + (max - min) * i as f64 / (RESOLUTION - 1) as f64)
+        .map(|y| {
+            let sum = values
+                .iter()
+                .map(|v| (-0.5 * ((y - v) / bandwidth).powi(2)).exp())
+                .sum::<f64>();
+            (y, sum / (n * bandwidth))
+        })
+        .collect()
+}
+
+impl Plotter for ViolinPlotter {
+    fn plot<DB>(
+        &self,
+        data: &Table,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<()>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        let columns = super::numeric_columns(data, &self.columns)?;
+        let mut chart =
+            super::category_chart(area, self.title.as_deref(), &columns)?;
+
+        for (i, column) in columns.iter().enumerate() {
+            let values = column.numeric_values();
+            if values.is_empty() {
+                continue;
+            }
+            let color = Palette99::pick(i).to_rgba();
+            let x = i as f64;
+
+            let density = density(&values);
+            let peak = density.iter().fold(0.0, |peak, (_, d)| d.max(peak));
+            let mut outline = density
+                .iter()
+                .map(|(y, d)| (x - 0.4 * d / peak, *y))
+                .chain(
+                    density.iter().rev().map(|(y, d)| (x + 0.4 * d / peak, *y)),
+                )
+                .collect::<Vec<(f64, f64)>>();
+            outline.push(outline[0]);
+            chart
+                .draw_series([Polygon::new(outline.clone(), color.mix(0.3))])?;
+            chart.draw_series([PathElement::new(
+                outline,
+                color.stroke_width(2),
+            )])?;
+
+            let q1 = statistics::percentile(&values, 25.0);
+            let q3 = statistics::percentile(&values, 75.0);
+            chart.draw_series([PathElement::new(
+                vec![(x, q1), (x, q3)],
+                BLACK.stroke_width(4),
+            )])?;
+            chart.draw_series([Circle::new(
+                (x, statistics::median(&values)),
+                3,
+                WHITE.filled(),
+            )])?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+//! Profilers which re-launch the running benchmark binary under an external
+//! profiling tool.
+
+mod nsys;
+pub use nsys::NsysProfiler;
+pub use nsys::NsysReport;
+
+#[cfg(feature = "sqlite")]
+mod nsys_export;
+#[cfg(feature = "sqlite")]
+pub use nsys_export::KernelMeasurement;
+#[cfg(feature = "sqlite")]
+pub use nsys_export::NsysExport;
+#[cfg(feature = "sqlite")]
+pub use nsys_export::NvtxRangeMeasurement;
@@ -0,0 +1,303 @@
+use anyhow::anyhow;
+use log::{info, trace};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::util;
+use crate::Result;
+
+/// Environment variable set in the process profiled by NsysProfiler, which
+/// then runs its benchmarks instead of re-launching itself.
+const PROFILED_ENV: &str = "VGPU_BENCH_NSYS_PROFILED";
+
+/// Extensions of Nsight Systems reports, by newer and older versions.
+const REPORT_EXTENSIONS: [&str; 2] = ["nsys-rep", "qdrep"];
+
+/// Files an NsysProfiler collected in the output directory.
+#[derive(Debug, Clone)]
+pub struct NsysReport {
+    /// The `.nsys-rep` or `.qdrep` report.
+    pub report: PathBuf,
+    /// The report exported to SQLite, unless export is disabled.
+    pub sqlite: Option<PathBuf>,
+}
+
+/// Re-launches the current binary with its arguments under
+/// `nsys profile`, collecting the report into the output directory and
+/// exporting it to SQLite and `nsys stats` CSV files.
+#[derive(Debug, Clone)]
+pub struct NsysProfiler {
+    nsys: PathBuf,
+    name: String,
+    trace: Vec<String>,
+    args: Vec<String>,
+    export: bool,
+    stats: bool,
+}
+
+impl Default for NsysProfiler {
+    /// Generates an NsysProfiler type with:
+    /// + nsys: 'nsys'
+    /// + name: 'profile'
+    /// + trace: [cuda, nvtx, osrt]
+    /// + args: []
+    /// + export: true
+    /// + stats: true
+    fn default() -> Self {
+        NsysProfiler {
+            nsys: PathBuf::from("nsys"),
+            name: String::from("profile"),
+            trace: vec!["cuda".into(), "nvtx".into(), "osrt".into()],
+            args: Vec::new(),
+            export: true,
+            stats: true,
+        }
+    }
+}
+
+impl NsysProfiler {
+    pub fn new() -> Self {
+        NsysProfiler::default()
+    }
+
+    /// Path of the `nsys` executable, e.g. a stub script in tests.
+    pub fn nsys<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.nsys = path.into();
+        self
+    }
+
+    /// File name of the report and its exports, without extension.
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// APIs traced by `nsys profile --trace`, e.g. `cuda`, `nvtx`, `osrt`,
+    /// `opengl` or `vulkan`.
+    pub fn trace<I, S>(mut self, apis: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trace = apis.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Additional argument of `nsys profile`, e.g. `--sample=none`.
+    pub fn arg<S>(mut self, arg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Whether to export the report to SQLite.
+    pub fn export(mut self, export: bool) -> Self {
+        self.export = export;
+        self
+    }
+
+    /// Whether to write the default `nsys stats` reports as CSV files.
+    pub fn stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Whether this process is the one being profiled.
+    pub fn is_profiled() -> bool {
+        std::env::var_os(PROFILED_ENV).is_some()
+    }
+
+    /// Runs the current binary with its arguments under `nsys profile`,
+    /// then collects the report and its exports in @param output_dir.
+    pub fn profile<P>(&self, output_dir: P) -> Result<NsysReport>
+    where
+        P: AsRef<Path>,
+    {
+        let output_dir = output_dir.as_ref();
+        util::io::dir_create_all(output_dir)?;
+        let base = output_dir.join(&self.name);
+
+        // Profile
+        let mut args: Vec<OsString> = vec![
+            "profile".into(),
+            format!("--trace={}", self.trace.join(",")).into(),
+            "--force-overwrite=true".into(),
+            format!("--env-var={PROFILED_ENV}=1").into(),
+            flag("--output=", &base),
+        ];
+        args.extend(self.args.iter().map(OsString::from));
+        args.push(std::env::current_exe()?.into());
+        args.extend(std::env::args_os().skip(1));
+        info!("profiling with {:?}", self.nsys);
+        // The profiled run logs its progress as it would without nsys
+        util::exec::run_program(self.nsys.clone().into_os_string(), args)?;
+        let report = self.collect_report(output_dir)?;
+        trace!("collected report {report:?}");
+
+        // Export
+        let sqlite = match self.export {
+            true => {
+                let sqlite = base.with_extension("sqlite");
+                let args: Vec<OsString> = vec![
+                    "export".into(),
+                    "--type=sqlite".into(),
+                    "--force-overwrite=true".into(),
+                    flag("--output=", &sqlite),
+                    report.clone().into(),
+                ];
+                self.call(args)?;
+                trace!("exported report to {sqlite:?}");
+                Some(sqlite)
+            }
+            false => None,
+        };
+
+        // Stats
+        if self.stats {
+            let input = sqlite.clone().unwrap_or_else(|| report.clone());
+            let args: Vec<OsString> = vec![
+                "stats".into(),
+                "--format=csv".into(),
+                flag("--output=", &base),
+                input.into(),
+            ];
+            self.call(args)?;
+            trace!("wrote stats of {report:?}");
+        }
+
+        Ok(NsysReport { report, sqlite })
+    }
+
+    /// Runs `nsys` with @param args.
+    fn call(&self, args: Vec<OsString>) -> Result<()> {
+        util::exec::call_program(self.nsys.clone().into_os_string(), args)?;
+        Ok(())
+    }
+
+    /// Finds the report of the last profile, moving it into
+    /// @param output_dir if nsys wrote it to the working directory.
+    fn collect_report(&self, output_dir: &Path) -> Result<PathBuf> {
+        for extension in REPORT_EXTENSIONS {
+            let file = format!("{}.{extension}", self.name);
+            let report = output_dir.join(&file);
+            if report.is_file() {
+                return Ok(report);
+            }
+            if Path::new(&file).is_file() {
+                std::fs::rename(&file, &report)?;
+                return Ok(report);
+            }
+        }
+        Err(anyhow!(
+            "{:?}: no {}.nsys-rep or {}.qdrep report was produced",
+            output_dir,
+            self.name,
+            self.name
+        ))
+    }
+}
+
+fn flag(name: &str, path: &Path) -> OsString {
+    let mut flag = OsString::from(name);
+    flag.push(path);
+    flag
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::util::testing::TempDir;
+
+    /// Stub of `nsys` logging its arguments to `args.log` next to it,
+    /// without running the profiled command, and touching the files nsys
+    /// would write.
+    const STUB: &str = r#"#!/bin/sh
+printf '%s\n' "$@" >> "$(dirname "$0")/args.log"
+echo >> "$(dirname "$0")/args.log"
+for arg in "$@"; do
+    case "$arg" in --output=*) output="${arg#--output=}" ;; esac
+done
+case "$1" in
+    profile) touch "$output.nsys-rep" ;;
+    export) touch "$output" ;;
+esac
+"#;
+
+    #[test]
+    fn profile_collects_the_report_and_export() -> Result<()> {
+        let dir = TempDir::new("nsys-stub");
+        let output_dir = dir.join("output");
+        let nsys = dir.join("nsys");
+        std::fs::write(&nsys, STUB)?;
+        std::fs::set_permissions(
+            &nsys,
+            std::fs::Permissions::from_mode(0o755),
+        )?;
+
+        let report = NsysProfiler::new()
+            .nsys(&nsys)
+            .name("run")
+            .trace(["cuda", "nvtx"])
+            .arg("--sample=none")
+            .stats(false)
+            .profile(&output_dir);
+        let report = report?;
+        let base = output_dir.join("run");
+        assert_eq!(report.report, base.with_extension("nsys-rep"));
+        assert_eq!(report.sqlite, Some(base.with_extension("sqlite")));
+        let log = std::fs::read_to_string(dir.join("args.log"))?;
+        let calls: Vec<Vec<&str>> = log
+            .split("\n\n")
+            .filter(|call| !call.is_empty())
+            .map(|call| call.lines().collect())
+            .collect();
+        assert_eq!(calls.len(), 2);
+        let exe = std::env::current_exe()?;
+        assert_eq!(
+            calls[0][..7],
+            [
+                "profile",
+                "--trace=cuda,nvtx",
+                "--force-overwrite=true",
+                "--env-var=VGPU_BENCH_NSYS_PROFILED=1",
+                &format!("--output={}", base.display()),
+                "--sample=none",
+                &exe.display().to_string(),
+            ]
+        );
+        assert_eq!(
+            calls[1],
+            [
+                "export",
+                "--type=sqlite",
+                "--force-overwrite=true",
+                &format!(
+                    "--output={}",
+                    base.with_extension("sqlite").display()
+                ),
+                &base.with_extension("nsys-rep").display().to_string(),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn profile_fails_without_nsys() {
+        let dir = TempDir::new("nsys-missing");
+        let report = NsysProfiler::new()
+            .nsys(dir.join("nsys"))
+            .profile(dir.join("output"));
+
+        assert!(report.is_err());
+    }
+}
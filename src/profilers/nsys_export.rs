@@ -0,0 +1,296 @@
+use log::trace;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::models::Measurements;
+use crate::Result;
+
+/// Prefix of the range BenchmarkFn::run wraps every benchmark in.
+const BENCHING: &str = "benching ";
+
+/// An NVTX range emitted inside the range of a benchmark, including the
+/// benchmark range itself.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NvtxRangeMeasurement {
+    pub benchmark: String,
+    pub range: String,
+    /// Ranges enclosing this one, 0 for the benchmark range.
+    pub depth: usize,
+    /// Time from the start of the benchmark range.
+    pub start_ns: i64,
+    pub duration_ns: i64,
+}
+
+/// A GPU kernel launched inside the range of a benchmark.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KernelMeasurement {
+    pub benchmark: String,
+    pub kernel: String,
+    pub device: i64,
+    pub stream: i64,
+    /// Time from the start of the benchmark range, which the kernel may
+    /// start after if it was launched asynchronously.
+    pub start_ns: i64,
+    pub duration_ns: i64,
+}
+
+/// An NVTX range of the export.
+struct Range {
+    name: String,
+    start: i64,
+    end: i64,
+    thread: i64,
+}
+
+impl Range {
+    fn contains(&self, time: i64) -> bool {
+        self.start <= time && time <= self.end
+    }
+
+    fn encloses(&self, other: &Range) -> bool {
+        self.thread == other.thread
+            && self.start <= other.start
+            && other.end <= self.end
+    }
+
+    /// Name of the benchmark this range is the `benching {name}` range of.
+    fn benchmark(&self) -> Option<&str> {
+        self.name.strip_prefix(BENCHING)
+    }
+}
+
+/// A SQLite database exported by `nsys export --type=sqlite`, read into
+/// Measurements keyed by the `benching {name}` range of every benchmark.
+pub struct NsysExport {
+    connection: Connection,
+}
+
+impl NsysExport {
+    /// Open the export at @param path, read-only.
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        trace!("opening nsys export {path:?}");
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        Ok(NsysExport { connection })
+    }
+
+    /// Names of the benchmarks profiled, in the order they ran.
+    pub fn benchmarks(&self) -> Result<Vec<String>> {
+        let ranges = self.nvtx_ranges()?;
+        Ok(ranges
+            .iter()
+            .filter_map(|range| range.benchmark().map(str::to_owned))
+            .collect())
+    }
+
+    /// Every NVTX range on the thread of a benchmark within its range.
+    pub fn ranges(&self) -> Result<Measurements<NvtxRangeMeasurement>> {
+        let ranges = self.nvtx_ranges()?;
+        let mut measurements = Measurements::new();
+        for bench in &ranges {
+            let benchmark = match bench.benchmark() {
+                Some(benchmark) => benchmark,
+                None => continue,
+            };
+            let inner: Vec<&Range> =
+                ranges.iter().filter(|r| bench.encloses(r)).collect();
+            for &range in &inner {
+                let depth = inner
+                    .iter()
+                    .filter(|&&r| !std::ptr::eq(r, range) && r.encloses(range))
+                    .count();
+                measurements.push(NvtxRangeMeasurement {
+                    benchmark: benchmark.to_owned(),
+                    range: range.name.clone(),
+                    depth,
+                    start_ns: range.start - bench.start,
+                    duration_ns: range.end - range.start,
+                });
+            }
+        }
+        Ok(measurements)
+    }
+
+    /// Every GPU kernel launched within the range of a benchmark, on its
+    /// thread. Without CUDA API traces, kernels are matched by when they
+    /// started instead.
+    pub fn kernels(&self) -> Result<Measurements<KernelMeasurement>> {
+        let mut measurements = Measurements::new();
+        if !self.has_table("CUPTI_ACTIVITY_KIND_KERNEL")? {
+            return Ok(measurements);
+        }
+        let ranges = self.nvtx_ranges()?;
+        let benches: Vec<&Range> =
+            ranges.iter().filter(|r| r.benchmark().is_some()).collect();
+
+        // Launches are correlated within a process, whose id is the global
+        // thread id without its lower 24 bits of thread id
+        let launch = match self.has_table("CUPTI_ACTIVITY_KIND_RUNTIME")? {
+            true => {
+                "r.start, r.globalTid
+                 FROM CUPTI_ACTIVITY_KIND_KERNEL AS k
+                 LEFT JOIN CUPTI_ACTIVITY_KIND_RUNTIME AS r
+                    ON r.correlationId = k.correlationId
+                    AND (r.globalTid & ~16777215) = k.globalPid"
+            }
+            false => "NULL, NULL FROM CUPTI_ACTIVITY_KIND_KERNEL AS k",
+        };
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT s.value, k.deviceId, k.streamId, k.start, k.end, {launch}
+             JOIN StringIds AS s ON s.id = k.shortName
+             ORDER BY k.start"
+        ))?;
+        let mut rows = statement.query(params![])?;
+        while let Some(row) = rows.next()? {
+            let (start, end): (i64, i64) = (row.get(3)?, row.get(4)?);
+            let launch: Option<i64> = row.get(5)?;
+            let thread: Option<i64> = row.get(6)?;
+            let bench = benches.iter().find(|b| match (launch, thread) {
+                (Some(launch), Some(thread)) => {
+                    b.thread == thread && b.contains(launch)
+                }
+                _ => b.contains(start),
+            });
+            if let Some(bench) = bench {
+                let benchmark = bench.benchmark().unwrap_or_default();
+                measurements.push(KernelMeasurement {
+                    benchmark: benchmark.to_owned(),
+                    kernel: row.get(0)?,
+                    device: row.get(1)?,
+                    stream: row.get(2)?,
+                    start_ns: start - bench.start,
+                    duration_ns: end - start,
+                });
+            }
+        }
+        Ok(measurements)
+    }
+
+    /// Every closed push/pop (59) and start/end (60) NVTX range, by start.
+    /// Older exports inline the name in `text`, newer ones may reference
+    /// `StringIds` by `textId` instead.
+    fn nvtx_ranges(&self) -> Result<Vec<Range>> {
+        if !self.has_table("NVTX_EVENTS")? {
+            return Ok(Vec::new());
+        }
+        let name = match self.has_column("NVTX_EVENTS", "textId")? {
+            true => {
+                "COALESCE(e.text, s.value) FROM NVTX_EVENTS AS e
+                 LEFT JOIN StringIds AS s ON s.id = e.textId"
+            }
+            false => "e.text FROM NVTX_EVENTS AS e",
+        };
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT e.start, e.end, e.globalTid, {name}
+             WHERE e.eventType IN (59, 60) AND e.end IS NOT NULL
+             ORDER BY e.start"
+        ))?;
+        let ranges = statement
+            .query_map(params![], |row| {
+                let thread: Option<i64> = row.get(2)?;
+                let name: Option<String> = row.get(3)?;
+                Ok(Range {
+                    start: row.get(0)?,
+                    end: row.get(1)?,
+                    thread: thread.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<Range>>>()?;
+        Ok(ranges)
+    }
+
+    fn has_table(&self, table: &str) -> Result<bool> {
+        let count: i64 = self.connection.query_row(
+            "SELECT COUNT(*) FROM sqlite_master
+             WHERE type = 'table' AND name = ?1",
+            params![table],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        let count: i64 = self.connection.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Result<NsysExport> {
+        NsysExport::open(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/nsys/sample.sqlite"
+        ))
+    }
+
+    #[test]
+    fn benchmarks_are_read_in_order() -> Result<()> {
+        assert_eq!(sample()?.benchmarks()?, ["tiger", "primitives"]);
+        Ok(())
+    }
+
+    #[test]
+    fn ranges_are_nested_within_their_benchmark() -> Result<()> {
+        let ranges: Vec<(String, String, usize, i64)> = sample()?
+            .ranges()?
+            .into_iter()
+            .map(|r| (r.benchmark, r.range, r.depth, r.start_ns))
+            .collect();
+        let expected = [
+            ("tiger", "benching tiger", 0, 0),
+            ("tiger", "tessellate", 1, 200000),
+            ("tiger", "render", 1, 3300000),
+            // Named by textId only
+            ("tiger", "upload", 2, 3400000),
+            ("primitives", "benching primitives", 0, 0),
+            ("primitives", "tessellate", 1, 500000),
+            ("primitives", "render", 1, 3600000),
+        ]
+        .map(|(benchmark, range, depth, start_ns)| {
+            (benchmark.to_owned(), range.to_owned(), depth, start_ns)
+        });
+
+        // The `loader` range of another thread is left out
+        assert_eq!(ranges, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn kernels_are_assigned_by_their_launch() -> Result<()> {
+        let kernels: Vec<(String, String, i64)> = sample()?
+            .kernels()?
+            .into_iter()
+            .map(|k| (k.benchmark, k.kernel, k.start_ns))
+            .collect();
+        let expected = [
+            ("tiger", "fill_paths", 4600000),
+            ("tiger", "resolve_coverage", 6100000),
+            // Launched within `tiger`, but ran after its range ended
+            ("tiger", "resolve_coverage", 8100000),
+            ("primitives", "fill_paths", 4100000),
+            ("primitives", "resolve_coverage", 5100000),
+        ]
+        .map(|(benchmark, kernel, start_ns)| {
+            (benchmark.to_owned(), kernel.to_owned(), start_ns)
+        });
+
+        // The `warmup` kernel launched before any benchmark is left out
+        assert_eq!(kernels, expected);
+        Ok(())
+    }
+}
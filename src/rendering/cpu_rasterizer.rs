@@ -0,0 +1,439 @@
+use anyhow::anyhow;
+use log::debug;
+use lyon::math::Point;
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex,
+    StrokeTessellator, StrokeVertex, VertexBuffers,
+};
+use std::path::{Path, PathBuf};
+use usvg::{NodeExt, NodeKind, Paint, Tree};
+
+use super::RgbaImage;
+use crate::util;
+use crate::Result;
+
+/// Triangles of one fill or stroke, in pixel coordinates, drawn in one color.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    pub buffers: VertexBuffers<Point, u32>,
+    /// Straight RGBA color, with the paint and group opacities in its alpha.
+    pub color: [u8; 4],
+}
+
+/// A tessellated document, ready to be rasterized.
+#[derive(Debug, Clone)]
+pub struct Scene {
+    pub width: u32,
+    pub height: u32,
+    /// Meshes in painting order.
+    pub meshes: Vec<Mesh>,
+}
+
+/// Reference rasterizer tessellating usvg trees with lyon and scanline
+/// rasterizing the triangles on the CPU, with supersampled anti-aliasing.
+///
+/// Only fills and strokes are drawn. Gradients are approximated by the mean
+/// of their stops, while dash arrays, patterns, images, clip paths, masks and
+/// filters are ignored, and group opacity is applied to each path separately.
+#[derive(Debug, Clone)]
+pub struct CpuRasterizer {
+    tolerance: f32,
+    samples: u32,
+    scale: f32,
+    background: [u8; 4],
+}
+
+impl Default for CpuRasterizer {
+    /// Generates a CpuRasterizer type with:
+    /// + tolerance: 0.1
+    /// + samples: 4
+    /// + scale: 1.0
+    /// + background: transparent
+    fn default() -> Self {
+        CpuRasterizer {
+            tolerance: FillOptions::DEFAULT_TOLERANCE,
+            samples: 4,
+            scale: 1.0,
+            background: [0, 0, 0, 0],
+        }
+    }
+}
+
+impl CpuRasterizer {
+    pub fn new() -> Self {
+        CpuRasterizer::default()
+    }
+
+    /// Tolerance curves are approximated to, in pixels.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Samples per pixel along each axis, e.g. 4 for 16 samples per pixel,
+    /// or 1 for no anti-aliasing.
+    pub fn samples(mut self, samples: u32) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Scale of the output image relative to the document size.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Color the image is cleared to before drawing.
+    pub fn background(mut self, background: [u8; 4]) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Renders the SVG file at @param path.
+    pub fn render_file<P>(&self, path: P) -> Result<RgbaImage>
+    where
+        P: AsRef<Path>,
+    {
+        self.render(&util::svg::parse(path)?)
+    }
+
+    /// Renders @param tree.
+    pub fn render(&self, tree: &Tree) -> Result<RgbaImage> {
+        Ok(self.rasterize(&self.tessellate(tree)?))
+    }
+
+    /// Renders every SVG file under @param svg_dir into a PNG file of the
+    /// same relative path under @param png_dir, returning the PNG paths.
+    pub fn render_dir<P, Q>(
+        &self,
+        svg_dir: P,
+        png_dir: Q,
+    ) -> Result<Vec<PathBuf>>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let svg_dir = svg_dir.as_ref();
+        let mut files =
+            util::io::get_files_with_extension(svg_dir, true, "svg");
+        files.sort();
+        let mut pngs = Vec::with_capacity(files.len());
+        for file in files {
+            // Files of the same name in different subdirectories are kept
+            let relative = file.strip_prefix(svg_dir).unwrap_or(&file);
+            let png = png_dir.as_ref().join(relative.with_extension("png"));
+            self.render_file(&file)?.write_png(&png)?;
+            pngs.push(png);
+        }
+        Ok(pngs)
+    }
+
+    /// Tessellates the fills and strokes of @param tree into pixel
+    /// coordinates of the output image.
+    pub fn tessellate(&self, tree: &Tree) -> Result<Scene> {
+        let svg = tree.svg_node();
+        let width = (svg.size.width() as f32 * self.scale).ceil().max(1.0);
+        let height = (svg.size.height() as f32 * self.scale).ceil().max(1.0);
+        let mut view_box_transform =
+            usvg::Transform::new_scale(self.scale as f64, self.scale as f64);
+        view_box_transform.append(&usvg::utils::view_box_to_transform(
+            svg.view_box.rect,
+            svg.view_box.aspect,
+            svg.size,
+        ));
+
+        let mut meshes = Vec::new();
+        let mut fill_tessellator = FillTessellator::new();
+        let mut stroke_tessellator = StrokeTessellator::new();
+        for node in tree.root().descendants() {
+            if tree.is_in_defs(&node) {
+                continue;
+            }
+            let kind = node.borrow();
+            let path = match *kind {
+                NodeKind::Path(ref path) => path,
+                _ => continue,
+            };
+            if path.visibility != usvg::Visibility::Visible {
+                continue;
+            }
+            let mut transform = view_box_transform;
+            transform.append(&node.abs_transform());
+            let lyon_path = util::svg::to_lyon_path(&path.data, &transform);
+            // Group opacities multiply rather than composite as layers
+            let group_opacity: f64 = node
+                .ancestors()
+                .filter_map(|n| match *n.borrow() {
+                    NodeKind::Group(ref group) => Some(group.opacity.value()),
+                    _ => None,
+                })
+                .product();
+
+            if let Some(ref fill) = path.fill {
+                let opacity = fill.opacity.value() * group_opacity;
+                if let Some(color) = paint_color(tree, &fill.paint, opacity) {
+                    let options = FillOptions::tolerance(self.tolerance)
+                        .with_fill_rule(util::svg::fill_rule(fill.rule));
+                    let mut buffers = VertexBuffers::new();
+                    fill_tessellator
+                        .tessellate_path(
+                            &lyon_path,
+                            &options,
+                            &mut BuffersBuilder::new(
+                                &mut buffers,
+                                |v: FillVertex| v.position(),
+                            ),
+                        )
+                        .map_err(|e| {
+                            anyhow!("fill tessellation failed: {e:?}")
+                        })?;
+                    meshes.push(Mesh { buffers, color });
+                }
+            }
+            if let Some(ref stroke) = path.stroke {
+                let opacity = stroke.opacity.value() * group_opacity;
+                if let Some(color) = paint_color(tree, &stroke.paint, opacity) {
+                    let scale = transform.get_scale();
+                    let width =
+                        stroke.width.value() * (scale.0 + scale.1) / 2.0;
+                    let options =
+                        util::svg::stroke_options(stroke, width as f32)
+                            .with_tolerance(self.tolerance);
+                    let mut buffers = VertexBuffers::new();
+                    stroke_tessellator
+                        .tessellate_path(
+                            &lyon_path,
+                            &options,
+                            &mut BuffersBuilder::new(
+                                &mut buffers,
+                                |v: StrokeVertex| v.position(),
+                            ),
+                        )
+                        .map_err(|e| {
+                            anyhow!("stroke tessellation failed: {e:?}")
+                        })?;
+                    meshes.push(Mesh { buffers, color });
+                }
+            }
+        }
+        Ok(Scene {
+            width: width as u32,
+            height: height as u32,
+            meshes,
+        })
+    }
+
+    /// Rasterizes the meshes of @param scene in order over the background.
+    pub fn rasterize(&self, scene: &Scene) -> RgbaImage {
+        let mut image = RgbaImage::new(scene.width, scene.height);
+        image.fill(self.background);
+        let mut coverage = Vec::new();
+        for mesh in &scene.meshes {
+            self.rasterize_mesh(&mut image, mesh, &mut coverage);
+        }
+        image
+    }
+
+    /// Marks the samples covered by any triangle of @param mesh within its
+    /// pixel bounds, then blends each pixel by its share of covered samples.
+    /// Triangles of one mesh may overlap without being blended twice.
+    fn rasterize_mesh(
+        &self,
+        image: &mut RgbaImage,
+        mesh: &Mesh,
+        coverage: &mut Vec<bool>,
+    ) {
+        let vertices = &mesh.buffers.vertices;
+        if vertices.is_empty() || mesh.color[3] == 0 {
+            return;
+        }
+        let (mut min, mut max) = (vertices[0], vertices[0]);
+        for v in vertices {
+            min = min.min(*v);
+            max = max.max(*v);
+        }
+        let x0 = min.x.floor().max(0.0) as u32;
+        let y0 = min.y.floor().max(0.0) as u32;
+        let x1 = (max.x.ceil().max(0.0) as u32).min(image.width());
+        let y1 = (max.y.ceil().max(0.0) as u32).min(image.height());
+        if x0 >= x1 || y0 >= y1 {
+            return;
+        }
+
+        // Sample grid over the pixel bounds of the mesh
+        let s = self.samples;
+        let columns = ((x1 - x0) * s) as usize;
+        let rows = ((y1 - y0) * s) as usize;
+        coverage.clear();
+        coverage.resize(columns * rows, false);
+        let to_sample = |p: Point| {
+            ((p.x - x0 as f32) * s as f32, (p.y - y0 as f32) * s as f32)
+        };
+        for triangle in mesh.buffers.indices.chunks_exact(3) {
+            let corners = [
+                to_sample(vertices[triangle[0] as usize]),
+                to_sample(vertices[triangle[1] as usize]),
+                to_sample(vertices[triangle[2] as usize]),
+            ];
+            scanline(&corners, columns, rows, coverage);
+        }
+
+        let samples_per_pixel = (s * s) as f32;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let mut covered = 0;
+                for sy in 0..s {
+                    let row = ((y - y0) * s + sy) as usize * columns;
+                    let column = ((x - x0) * s) as usize;
+                    covered += coverage
+                        [row + column..row + column + s as usize]
+                        .iter()
+                        .filter(|&&c| c)
+                        .count();
+                }
+                if covered > 0 {
+                    let share = covered as f32 / samples_per_pixel;
+                    image.blend_pixel(x, y, mesh.color, share);
+                }
+            }
+        }
+    }
+}
+
+/// Marks the samples of a @param columns by @param rows grid whose centers
+/// fall within the triangle @param corners, one row of samples at a time.
+fn scanline(
+    corners: &[(f32, f32); 3],
+    columns: usize,
+    rows: usize,
+    coverage: &mut [bool],
+) {
+    let top = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min);
+    let bottom = corners
+        .iter()
+        .map(|c| c.1)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let first_row = (top - 0.5).ceil().max(0.0) as usize;
+    let last_row = ((bottom - 0.5).ceil().max(0.0) as usize).min(rows);
+
+    for row in first_row..last_row {
+        let y = row as f32 + 0.5;
+        // Crossings of the sample row with the edges spanning it
+        let mut left = f32::INFINITY;
+        let mut right = f32::NEG_INFINITY;
+        for i in 0..3 {
+            let (a, b) = (corners[i], corners[(i + 1) % 3]);
+            let (upper, lower) = if a.1 <= b.1 { (a, b) } else { (b, a) };
+            if y < upper.1 || y >= lower.1 {
+                continue;
+            }
+            let t = (y - upper.1) / (lower.1 - upper.1);
+            let x = upper.0 + t * (lower.0 - upper.0);
+            left = left.min(x);
+            right = right.max(x);
+        }
+        if left >= right {
+            continue;
+        }
+        let first = (left - 0.5).ceil().max(0.0) as usize;
+        let last = ((right - 0.5).ceil().max(0.0) as usize).min(columns);
+        if first < last {
+            coverage[row * columns + first..row * columns + last].fill(true);
+        }
+    }
+}
+
+/// Straight RGBA color of @param paint at @param opacity, approximating
+/// gradients by the mean of their stops, or None if it cannot be drawn.
+fn paint_color(tree: &Tree, paint: &Paint, opacity: f64) -> Option<[u8; 4]> {
+    let (rgb, alpha) = match paint {
+        Paint::Color(color) => ([color.red, color.green, color.blue], 1.0),
+        Paint::Link(id) => {
+            let node = tree.defs_by_id(id)?;
+            let stops = match *node.borrow() {
+                NodeKind::LinearGradient(ref gradient) => {
+                    gradient.stops.clone()
+                }
+                NodeKind::RadialGradient(ref gradient) => {
+                    gradient.stops.clone()
+                }
+                _ => {
+                    debug!("'{id}': only solid colors and gradients are drawn");
+                    return None;
+                }
+            };
+            if stops.is_empty() {
+                return None;
+            }
+            let n = stops.len() as f64;
+            let mean = |channel: fn(&usvg::Stop) -> f64| {
+                stops.iter().map(channel).sum::<f64>() / n
+            };
+            (
+                [
+                    mean(|s| s.color.red as f64).round() as u8,
+                    mean(|s| s.color.green as f64).round() as u8,
+                    mean(|s| s.color.blue as f64).round() as u8,
+                ],
+                mean(|s| s.opacity.value()),
+            )
+        }
+    };
+    let alpha = (alpha * opacity * 255.0).round() as u8;
+    Some([rgb[0], rgb[1], rgb[2], alpha])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    /// A horizontal line from x = 4 to 16 across the middle of a 20x20
+    /// document, stroked with @param cap.
+    fn line(cap: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+                <path d="M4 10 H16" stroke="black" stroke-width="6"
+                    stroke-linecap="{cap}"/>
+            </svg>"#
+        )
+    }
+
+    fn render(svg: &str) -> Result<RgbaImage> {
+        let tree =
+            Tree::from_data(svg.as_bytes(), &usvg::Options::default().to_ref())
+                .map_err(|e| anyhow!("{e}"))?;
+        CpuRasterizer::new().samples(1).render(&tree)
+    }
+
+    #[test]
+    fn strokes_are_capped() -> Result<()> {
+        let butt = render(&line("butt"))?;
+        let round = render(&line("round"))?;
+
+        // Round caps extend half the stroke width past the end points
+        assert_eq!(butt.pixel(2, 10)[3], 0);
+        assert_eq!(round.pixel(2, 10)[3], 255);
+        Ok(())
+    }
+
+    #[test]
+    fn render_dir_keeps_relative_paths() -> Result<()> {
+        let dir = TempDir::new("render-dir");
+        let (svg_dir, png_dir) = (dir.join("svg"), dir.join("png"));
+        for sub in ["a", "b"] {
+            std::fs::create_dir_all(svg_dir.join(sub))?;
+            std::fs::write(svg_dir.join(sub).join("line.svg"), line("butt"))?;
+        }
+        let pngs = CpuRasterizer::new().render_dir(&svg_dir, &png_dir);
+        let exist = [png_dir.join("a/line.png"), png_dir.join("b/line.png")]
+            .map(|png| png.is_file());
+
+        assert_eq!(
+            pngs?,
+            vec![png_dir.join("a/line.png"), png_dir.join("b/line.png")]
+        );
+        assert_eq!(exist, [true, true]);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anyhow::anyhow;
+use usvg::Tree;
+
+use super::{CpuRasterizer, Renderer, RgbaImage, Scene};
+use crate::Result;
+
+/// Renderer over the CpuRasterizer, tessellating in `prepare` and
+/// rasterizing in `render`.
+pub struct CpuRenderer {
+    rasterizer: CpuRasterizer,
+    tree: Option<Tree>,
+    scene: Option<Scene>,
+}
+
+impl Default for CpuRenderer {
+    /// Generates a CpuRenderer with CpuRasterizer::default().
+    fn default() -> Self {
+        CpuRenderer::new(CpuRasterizer::default())
+    }
+}
+
+impl CpuRenderer {
+    /// Generates a CpuRenderer drawing with @param rasterizer.
+    pub fn new(rasterizer: CpuRasterizer) -> Self {
+        CpuRenderer {
+            rasterizer,
+            tree: None,
+            scene: None,
+        }
+    }
+}
+
+impl Renderer for CpuRenderer {
+    fn name(&self) -> &str {
+        "cpu"
+    }
+
+    fn load(&mut self, tree: &Tree) -> Result<()> {
+        self.tree = Some(tree.clone());
+        self.scene = None;
+        Ok(())
+    }
+
+    fn prepare(&mut self) -> Result<()> {
+        let tree = self
+            .tree
+            .as_ref()
+            .ok_or_else(|| anyhow!("no document loaded"))?;
+        self.scene = Some(self.rasterizer.tessellate(tree)?);
+        Ok(())
+    }
+
+    fn render(&mut self) -> Result<RgbaImage> {
+        let scene = self
+            .scene
+            .as_ref()
+            .ok_or_else(|| anyhow!("no document prepared"))?;
+        Ok(self.rasterizer.rasterize(scene))
+    }
+
+    fn teardown(&mut self) -> Result<()> {
+        self.tree = None;
+        self.scene = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_need_the_previous_ones() -> Result<()> {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+    <rect width="2" height="2"/>
+</svg>"#;
+        let tree = Tree::from_str(svg, &usvg::Options::default().to_ref())?;
+        let mut renderer = CpuRenderer::default();
+
+        assert!(renderer.prepare().is_err());
+        renderer.load(&tree)?;
+        assert!(renderer.render().is_err());
+        renderer.prepare()?;
+        let image = renderer.render()?;
+        assert_eq!((image.width(), image.height()), (4, 4));
+        // Loading a document drops the scene of the last one
+        renderer.load(&tree)?;
+        assert!(renderer.render().is_err());
+        renderer.teardown()?;
+        assert!(renderer.prepare().is_err());
+        Ok(())
+    }
+}
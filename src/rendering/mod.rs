@@ -0,0 +1,16 @@
+//! Rendering of SVG documents into images.
+
+mod cpu_rasterizer;
+pub use cpu_rasterizer::CpuRasterizer;
+pub use cpu_rasterizer::Mesh;
+pub use cpu_rasterizer::Scene;
+
+mod cpu_renderer;
+pub use cpu_renderer::CpuRenderer;
+
+mod renderer;
+pub use renderer::RenderStage;
+pub use renderer::Renderer;
+
+mod rgba_image;
+pub use rgba_image::RgbaImage;
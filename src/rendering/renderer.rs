@@ -0,0 +1,56 @@
+use usvg::Tree;
+
+use super::RgbaImage;
+use crate::Result;
+
+/// Stages of rendering, in the order a Renderer goes through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStage {
+    Init,
+    Load,
+    Prepare,
+    Render,
+    Teardown,
+}
+
+impl RenderStage {
+    /// Name of the stage, e.g. `prepare`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RenderStage::Init => "init",
+            RenderStage::Load => "load",
+            RenderStage::Prepare => "prepare",
+            RenderStage::Render => "render",
+            RenderStage::Teardown => "teardown",
+        }
+    }
+}
+
+/// A vector graphics rendering backend, driven through each RenderStage so
+/// every stage can be timed on its own.
+///
+/// A backend is initialized once, then loads, prepares and renders any
+/// number of documents before it is torn down.
+pub trait Renderer {
+    /// Name of the backend, recorded with its measurements.
+    fn name(&self) -> &str;
+
+    /// Acquire the resources of the backend, such as a device or context.
+    fn init(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Load the document @param tree, replacing the last one.
+    fn load(&mut self, tree: &Tree) -> Result<()>;
+
+    /// Prepare the loaded document for drawing, e.g. tessellate and upload.
+    fn prepare(&mut self) -> Result<()>;
+
+    /// Draw a frame of the prepared document into an image.
+    fn render(&mut self) -> Result<RgbaImage>;
+
+    /// Release the resources acquired by `init`.
+    fn teardown(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{anyhow, ensure};
+use png::{BitDepth, ColorType, Transformations};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use crate::util;
+use crate::Result;
+
+/// An 8-bit RGBA image with straight (non-premultiplied) alpha, stored in
+/// rows from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// A transparent image of @param width by @param height pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        RgbaImage {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// An image of @param width by @param height pixels over the RGBA bytes
+    /// @param data.
+    pub fn from_raw(width: u32, height: u32, data: Vec<u8>) -> Result<Self> {
+        ensure!(
+            data.len() == width as usize * height as usize * 4,
+            "{} bytes do not make a {width}x{height} RGBA image",
+            data.len()
+        );
+        Ok(RgbaImage {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The RGBA bytes of every pixel, row by row.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The pixel at column @param x and row @param y.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = self.index(x, y);
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+
+    /// Replaces the pixel at column @param x and row @param y.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let i = self.index(x, y);
+        self.data[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// Replaces every pixel with @param color.
+    pub fn fill(&mut self, color: [u8; 4]) {
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    /// Composites @param color over the pixel at column @param x and row
+    /// @param y, with its alpha scaled by @param coverage from 0 to 1.
+    pub fn blend_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        color: [u8; 4],
+        coverage: f32,
+    ) {
+        let i = self.index(x, y);
+        let src_alpha = color[3] as f32 / 255.0 * coverage.clamp(0.0, 1.0);
+        if src_alpha <= 0.0 {
+            return;
+        }
+        let dst_alpha = self.data[i + 3] as f32 / 255.0;
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+        for (dst, &src) in self.data[i..i + 3].iter_mut().zip(&color) {
+            let src = src as f32 * src_alpha;
+            let under = *dst as f32 * dst_alpha * (1.0 - src_alpha);
+            *dst = ((src + under) / out_alpha).round() as u8;
+        }
+        self.data[i + 3] = (out_alpha * 255.0).round() as u8;
+    }
+
+    /// Reads the PNG file at @param path, converting it to 8-bit RGBA.
+    pub fn read_png<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+
+        let data = match info.color_type {
+            ColorType::Rgba => buffer,
+            ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            ColorType::Grayscale => {
+                buffer.iter().flat_map(|&v| [v, v, v, 255]).collect()
+            }
+            ColorType::Indexed => {
+                return Err(anyhow!("{path:?}: unexpanded indexed colors"))
+            }
+        };
+        RgbaImage::from_raw(info.width, info.height, data)
+    }
+
+    /// Writes this image as an 8-bit RGBA PNG file to @param path.
+    pub fn write_png<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            util::io::dir_create_all(parent)?;
+        }
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+}
@@ -0,0 +1,277 @@
+use log::{trace, warn};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{BenchmarkSummary, MetricSummary};
+use crate::models::{
+    DriverBundle, Measurements, MonitorBundle, Plotter, Record, Table,
+};
+use crate::plotters::{HistogramPlotter, ViolinPlotter};
+use crate::util;
+use crate::Result;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 1200px; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; }
+th { background: #f0f0f0; text-align: left; }
+td.number { text-align: right; font-family: monospace; }
+.completed { color: #2a7a2a; }
+.failed { color: #b00020; }
+.plots { display: grid; grid-template-columns: 1fr 1fr; gap: 1em; }
+.plots svg { width: 100%; height: auto; }
+pre { background: #f8f8f8; padding: 1em; overflow-x: auto; }
+";
+
+/// Self-contained HTML report of a Driver output directory: the run's
+/// environment, the outcome and summary statistics of every benchmark,
+/// plots of their measurements and monitor timelines, and failure details.
+pub struct Report {
+    bundle: DriverBundle<Record>,
+    summaries: Measurements<MetricSummary>,
+}
+
+impl Report {
+    /// Reads an output directory written by a Driver run with CSV output.
+    pub fn read<P>(dir: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        trace!("reading report data from {dir:?}");
+
+        let bundle = DriverBundle::<Record>::read(dir)?;
+        let mut summaries = Measurements::read(dir.join("summary.csv"))?;
+        if summaries.is_empty() {
+            summaries = bundle.metric_summaries()?;
+        }
+
+        Ok(Report { bundle, summaries })
+    }
+
+    /// Writes `report.html` in @param dir, returning its path.
+    pub fn write<P>(&self, dir: P) -> Result<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        util::io::create_data_landing(dir)?;
+
+        let path = dir.join("report.html");
+        trace!("writing report to {path:?}");
+        std::fs::write(&path, self.to_html()?)?;
+        Ok(path)
+    }
+
+    /// Renders the report as a single HTML document with inline SVG plots.
+    pub fn to_html(&self) -> Result<String> {
+        let environment = &self.bundle.environment;
+        let benchmarks = self.bundle.benchmark_summaries();
+
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        html.push_str("<title>vgpu-bench report</title>\n");
+        let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+        html.push_str("<h1>vgpu-bench report</h1>\n");
+
+        // Environment
+        html.push_str("<h2>Environment</h2>\n<table>\n");
+        for (key, value) in [
+            ("Started", format_timestamp(environment.timestamp)),
+            ("Host", environment.hostname.clone()),
+            ("OS", format!("{} ({})", environment.os, environment.family)),
+            ("Architecture", environment.arch.clone()),
+            ("CPUs", environment.cpus.to_string()),
+            ("vgpu-bench", environment.version.clone()),
+        ] {
+            let _ = writeln!(
+                html,
+                "<tr><th>{key}</th><td>{}</td></tr>",
+                escape(&value)
+            );
+        }
+        html.push_str("</table>\n");
+
+        // Overview
+        html.push_str("<h2>Benchmarks</h2>\n<table>\n");
+        html.push_str("<tr><th>Benchmark</th><th>Status</th>");
+        html.push_str("<th>Duration (ms)</th></tr>\n");
+        for benchmark in &benchmarks {
+            let name = escape(&benchmark.benchmark);
+            let (status, duration) = match benchmark.completed {
+                true => ("completed", benchmark.duration_ns as f64 / 1e6),
+                false => ("failed", f64::NAN),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td><a href=\"#{name}\">{name}</a></td>\
+                 <td class=\"{status}\">{status}</td>\
+                 <td class=\"number\">{}</td></tr>",
+                format_number(duration)
+            );
+        }
+        html.push_str("</table>\n");
+
+        // Failures
+        let failures = benchmarks.iter().filter(|b| !b.completed);
+        for (i, benchmark) in failures.enumerate() {
+            if i == 0 {
+                html.push_str("<h2>Failures</h2>\n");
+            }
+            let name = escape(&benchmark.benchmark);
+            let error = benchmark.error.as_deref().unwrap_or_default();
+            let _ = writeln!(
+                html,
+                "<h3 id=\"{name}\">{name}</h3>\n<pre>{}</pre>",
+                escape(error)
+            );
+        }
+
+        // Completed benchmarks
+        for benchmark in benchmarks.iter().filter(|b| b.completed) {
+            self.push_benchmark(&mut html, benchmark)?;
+        }
+
+        html.push_str("</body>\n</html>\n");
+        Ok(html)
+    }
+
+    fn push_benchmark(
+        &self,
+        html: &mut String,
+        benchmark: &BenchmarkSummary,
+    ) -> Result<()> {
+        let name = &benchmark.benchmark;
+        let bundle = match self.bundle.benchmark_bundles.get(name) {
+            Some(bundle) => bundle,
+            None => return Ok(()),
+        };
+        let _ = writeln!(html, "<h2 id=\"{0}\">{0}</h2>", escape(name));
+
+        // Summary statistics
+        html.push_str("<table>\n<tr><th>Metric</th><th>Count</th>");
+        html.push_str("<th>Mean</th><th>Std dev</th><th>Min</th>");
+        html.push_str("<th>Median</th><th>p95</th><th>Max</th></tr>\n");
+        for summary in self.summaries.iter().filter(|s| s.benchmark == *name) {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td class=\"number\">{}</td>",
+                escape(&summary.metric),
+                summary.count
+            );
+            for value in [
+                summary.mean,
+                summary.std_dev,
+                summary.min,
+                summary.median,
+                summary.p95,
+                summary.max,
+            ] {
+                let _ = write!(
+                    html,
+                    "<td class=\"number\">{}</td>",
+                    format_number(value)
+                );
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        // Measurement plots
+        let table = Table::from_measurements(&bundle.measurements)?;
+        html.push_str("<div class=\"plots\">\n");
+        for column in table.numeric_columns() {
+            let metric = column.name();
+            let histogram = HistogramPlotter::new(metric).title(metric);
+            push_plot(html, &histogram, &table, metric);
+            let violin = ViolinPlotter::new().columns([metric]).title(metric);
+            push_plot(html, &violin, &table, metric);
+        }
+        html.push_str("</div>\n");
+
+        // Monitor timelines
+        let monitors = bundle.monitor_bundle.tables()?;
+        if !monitors.is_empty() {
+            html.push_str("<h3>Monitors</h3>\n<div class=\"plots\">\n");
+            for (monitor, table) in monitors {
+                let timeline =
+                    MonitorBundle::timeline(&monitor, bundle.duration);
+                push_plot(html, &timeline, &table, &monitor);
+            }
+            html.push_str("</div>\n");
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends an inline SVG plot, or a note if the plot could not be drawn.
+fn push_plot<P>(html: &mut String, plotter: &P, table: &Table, name: &str)
+where
+    P: Plotter,
+{
+    match plotter.plot_svg(table) {
+        Ok(svg) => {
+            html.push_str(&svg);
+            html.push('\n');
+        }
+        Err(e) => {
+            warn!("{name}: failed to plot with error '{e}'");
+            let _ = writeln!(html, "<p>{}: no plot</p>", escape(name));
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Three decimal places, or scientific notation for very large or small
+/// magnitudes.
+fn format_number(value: f64) -> String {
+    let magnitude = value.abs();
+    if value.is_nan() {
+        String::from("-")
+    } else if magnitude != 0.0 && !(1e-3..1e6).contains(&magnitude) {
+        format!("{value:.3e}")
+    } else {
+        format!("{value:.3}")
+    }
+}
+
+/// Formats seconds since the UNIX epoch as a UTC date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return String::from("unknown");
+    }
+    let (days, seconds) = ((timestamp / 86400) as i64, timestamp % 86400);
+
+    // Civil date from days since the epoch, after Howard Hinnant
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,2 @@
+mod html;
+pub use html::Report;
@@ -0,0 +1,5 @@
+//! Built-in benchmark suites over the bundled assets.
+
+pub mod parsing;
+pub mod rendering;
+pub mod tessellation;
@@ -0,0 +1,188 @@
+use anyhow::anyhow;
+use log::warn;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use usvg::{NodeKind, Tree};
+
+use crate::macros::measurement;
+use crate::models::{Benchmark, BenchmarkFn, BenchmarkMetadata, Measurements};
+use crate::util;
+use crate::util::alloc::AllocationStats;
+use crate::Result;
+
+/// Parse cost and resulting tree size of one SVG file with one set of usvg
+/// options.
+#[measurement]
+pub struct ParsingMeasurement {
+    pub file: String,
+    pub options: String,
+    pub file_bytes: usize,
+    pub parse_time_ns: u128,
+    /// Bytes allocated while parsing, zero unless a CountingAllocator is
+    /// installed, e.g. by building with the `alloc-counting` feature.
+    pub allocated_bytes: u64,
+    pub allocations: u64,
+    /// Bytes still allocated after parsing, held by the tree.
+    pub retained_bytes: u64,
+    pub groups: usize,
+    pub paths: usize,
+    pub images: usize,
+    pub gradients: usize,
+    pub patterns: usize,
+    pub clip_paths: usize,
+    pub masks: usize,
+    pub filters: usize,
+    pub path_segments: usize,
+}
+
+/// Suite measuring `usvg::Tree::from_data` over SVG files, with one or more
+/// variations of `usvg::Options` (e.g. DPI, shape rendering or text).
+pub struct ParsingSuite {
+    files: Vec<PathBuf>,
+    options: Vec<(String, usvg::Options)>,
+    iterations: usize,
+}
+
+impl Default for ParsingSuite {
+    /// Generates a ParsingSuite type with:
+    /// + files: []
+    /// + options: [("default", usvg::Options::default())]
+    /// + iterations: 1
+    fn default() -> Self {
+        ParsingSuite {
+            files: Vec::new(),
+            options: vec![(String::from("default"), usvg::Options::default())],
+            iterations: 1,
+        }
+    }
+}
+
+impl ParsingSuite {
+    pub fn new() -> Self {
+        ParsingSuite::default()
+    }
+
+    /// Add SVG files to the suite.
+    pub fn files<I, P>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.files.extend(files.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add every SVG file under @param dir to the suite, in name order.
+    pub fn dir<P>(self, dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let mut files = util::io::get_files_with_extension(dir, true, "svg");
+        files.sort();
+        self.files(files)
+    }
+
+    /// Select the usvg options files are parsed with, each labelled by name
+    /// in the measurements, replacing the default options.
+    pub fn options<I, S>(mut self, options: I) -> Self
+    where
+        I: IntoIterator<Item = (S, usvg::Options)>,
+        S: Into<String>,
+    {
+        self.options = options
+            .into_iter()
+            .map(|(name, options)| (name.into(), options))
+            .collect();
+        self
+    }
+
+    /// Number of times every file is parsed with every set of options.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Parses every file, once per iteration, with every set of options.
+    pub fn run(&self) -> Result<Measurements<ParsingMeasurement>> {
+        if !util::alloc::is_installed() {
+            warn!("no CountingAllocator installed, allocations read as 0");
+        }
+
+        let mut measurements = Measurements::new();
+        for _ in 0..self.iterations {
+            for path in &self.files {
+                let file = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let data = std::fs::read(path)?;
+
+                for (name, options) in &self.options {
+                    let options = options.to_ref();
+                    let before = AllocationStats::current();
+                    let start = Instant::now();
+                    let tree = Tree::from_data(&data, &options);
+                    let parse_time_ns = start.elapsed().as_nanos();
+                    let allocation = AllocationStats::current().since(&before);
+                    let tree = tree.map_err(|e| {
+                        anyhow!("{path:?}: failed to parse with error '{e}'")
+                    })?;
+
+                    let mut measurement = ParsingMeasurement {
+                        file: file.clone(),
+                        options: name.clone(),
+                        file_bytes: data.len(),
+                        parse_time_ns,
+                        allocated_bytes: allocation.allocated_bytes,
+                        allocations: allocation.allocations,
+                        retained_bytes: allocation.retained_bytes(),
+                        groups: 0,
+                        paths: 0,
+                        images: 0,
+                        gradients: 0,
+                        patterns: 0,
+                        clip_paths: 0,
+                        masks: 0,
+                        filters: 0,
+                        path_segments: 0,
+                    };
+                    measurement.count_nodes(&tree);
+                    measurements.push(measurement);
+                }
+            }
+        }
+        Ok(measurements)
+    }
+
+    /// Benchmark named @param name running this suite.
+    pub fn benchmark<S>(self, name: S) -> Benchmark<ParsingMeasurement>
+    where
+        S: Into<String>,
+    {
+        let metadata = BenchmarkMetadata::new(name);
+        Benchmark::new(metadata, BenchmarkFn::new(move || self.run()))
+    }
+}
+
+impl ParsingMeasurement {
+    fn count_nodes(&mut self, tree: &Tree) {
+        for node in tree.root().descendants() {
+            match *node.borrow() {
+                NodeKind::Group(_) => self.groups += 1,
+                NodeKind::Path(ref path) => {
+                    self.paths += 1;
+                    self.path_segments += path.data.len();
+                }
+                NodeKind::Image(_) => self.images += 1,
+                NodeKind::LinearGradient(_) | NodeKind::RadialGradient(_) => {
+                    self.gradients += 1
+                }
+                NodeKind::Pattern(_) => self.patterns += 1,
+                NodeKind::ClipPath(_) => self.clip_paths += 1,
+                NodeKind::Mask(_) => self.masks += 1,
+                NodeKind::Filter(_) => self.filters += 1,
+                NodeKind::Svg(_) | NodeKind::Defs => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use anyhow::anyhow;
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::macros::measurement;
+use crate::models::{Benchmark, BenchmarkFn, BenchmarkMetadata, Measurements};
+use crate::rendering::{RenderStage, Renderer};
+use crate::util;
+use crate::Result;
+
+/// Cost of each stage of rendering one SVG file with one Renderer.
+#[measurement]
+pub struct RenderingMeasurement {
+    pub renderer: String,
+    pub file: String,
+    pub width: u32,
+    pub height: u32,
+    /// Time to parse the file with usvg.
+    pub parse_time_ns: u128,
+    pub load_time_ns: u128,
+    pub prepare_time_ns: u128,
+    pub render_time_ns: u128,
+}
+
+/// Suite rendering SVG files with any Renderer, optionally writing the
+/// images as PNG files, e.g. as references or for an AccuracyCheck.
+pub struct RenderingSuite<R>
+where
+    R: Renderer,
+{
+    renderer: R,
+    /// SVG files with the path of their PNG files within `png_dir`.
+    files: Vec<(PathBuf, PathBuf)>,
+    iterations: usize,
+    png_dir: Option<PathBuf>,
+    hooks: Vec<Box<dyn Fn(RenderStage, Duration)>>,
+}
+
+impl<R> RenderingSuite<R>
+where
+    R: Renderer + 'static,
+{
+    /// Generates a RenderingSuite of @param renderer with:
+    /// + files: []
+    /// + iterations: 1
+    /// + png_dir: None
+    /// + hooks: []
+    pub fn new(renderer: R) -> Self {
+        RenderingSuite {
+            renderer,
+            files: Vec::new(),
+            iterations: 1,
+            png_dir: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Add SVG files to the suite.
+    pub fn files<I, P>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.files.extend(files.into_iter().map(|path| {
+            let path = path.into();
+            let png = path.file_name().map(PathBuf::from).unwrap_or_default();
+            (path, png.with_extension("png"))
+        }));
+        self
+    }
+
+    /// Add every SVG file under @param dir to the suite, in name order. Their
+    /// PNG files keep their paths relative to @param dir.
+    pub fn dir<P>(mut self, dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut files = util::io::get_files_with_extension(dir, true, "svg");
+        files.sort();
+        self.files.extend(files.into_iter().map(|path| {
+            let png = path.strip_prefix(dir).unwrap_or(&path);
+            let png = png.with_extension("png");
+            (path, png)
+        }));
+        self
+    }
+
+    /// Number of times every file is rendered.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Write the image of every file to @param dir, as a PNG file of the same
+    /// name, on the first iteration. Files added by `dir` keep their
+    /// subdirectories.
+    pub fn png_dir<P>(mut self, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.png_dir = Some(dir.into());
+        self
+    }
+
+    /// Call @param hook with the duration of every stage as it completes,
+    /// including `init` and `teardown`.
+    pub fn on_stage<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(RenderStage, Duration) + 'static,
+    {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Initializes the renderer, renders every file once per iteration and
+    /// tears the renderer down, even if rendering failed.
+    pub fn run(&mut self) -> Result<Measurements<RenderingMeasurement>> {
+        self.stage(RenderStage::Init, |renderer| renderer.init())?;
+        let measurements = self.render_files();
+        let teardown =
+            self.stage(RenderStage::Teardown, |renderer| renderer.teardown());
+        let measurements = measurements?;
+        teardown?;
+        Ok(measurements)
+    }
+
+    /// Benchmark named @param name running this suite.
+    pub fn benchmark<S>(mut self, name: S) -> Benchmark<RenderingMeasurement>
+    where
+        S: Into<String>,
+    {
+        let metadata = BenchmarkMetadata::new(name);
+        Benchmark::new(metadata, BenchmarkFn::new(move || self.run()))
+    }
+
+    fn render_files(&mut self) -> Result<Measurements<RenderingMeasurement>> {
+        let mut measurements = Measurements::new();
+        for iteration in 0..self.iterations {
+            for (path, png) in self.files.clone() {
+                let png = (iteration == 0).then_some(png.as_path());
+                let measurement = self
+                    .render_file(&path, png)
+                    .map_err(|e| anyhow!("{path:?}: {e}"))?;
+                measurements.push(measurement);
+            }
+        }
+        Ok(measurements)
+    }
+
+    /// Renders the SVG file at @param path, writing its image to @param png
+    /// within `png_dir`, if any.
+    fn render_file(
+        &mut self,
+        path: &Path,
+        png: Option<&Path>,
+    ) -> Result<RenderingMeasurement> {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let start = Instant::now();
+        let tree = util::svg::parse(path)?;
+        let parse_time_ns = start.elapsed().as_nanos();
+
+        let ((), load_time_ns) =
+            self.stage(RenderStage::Load, |renderer| renderer.load(&tree))?;
+        let ((), prepare_time_ns) =
+            self.stage(RenderStage::Prepare, |renderer| renderer.prepare())?;
+        let (image, render_time_ns) =
+            self.stage(RenderStage::Render, |renderer| renderer.render())?;
+
+        if let (Some(png), Some(dir)) = (png, &self.png_dir) {
+            image.write_png(dir.join(png))?;
+        }
+        Ok(RenderingMeasurement {
+            renderer: self.renderer.name().to_owned(),
+            file,
+            width: image.width(),
+            height: image.height(),
+            parse_time_ns,
+            load_time_ns,
+            prepare_time_ns,
+            render_time_ns,
+        })
+    }
+
+    /// Runs @param stage of the renderer with @param func, reporting its
+    /// duration to every hook.
+    fn stage<T, F>(&mut self, stage: RenderStage, func: F) -> Result<(T, u128)>
+    where
+        F: FnOnce(&mut R) -> Result<T>,
+    {
+        let start = Instant::now();
+        let value = func(&mut self.renderer)?;
+        let elapsed = start.elapsed();
+        if matches!(stage, RenderStage::Init | RenderStage::Teardown) {
+            debug!("{}: {} in {elapsed:?}", self.renderer.name(), stage.name());
+        }
+        for hook in &self.hooks {
+            hook(stage, elapsed);
+        }
+        Ok((value, elapsed.as_nanos()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use usvg::Tree;
+
+    use super::*;
+    use crate::rendering::{CpuRenderer, RgbaImage};
+    use crate::util::testing::TempDir;
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+    <rect width="2" height="2"/>
+</svg>"#;
+
+    /// Renderer recording the stages it goes through, failing to render
+    /// when told to.
+    struct Recorder {
+        stages: Rc<RefCell<Vec<RenderStage>>>,
+        fail: bool,
+    }
+
+    impl Renderer for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn init(&mut self) -> Result<()> {
+            self.stages.borrow_mut().push(RenderStage::Init);
+            Ok(())
+        }
+
+        fn load(&mut self, _tree: &Tree) -> Result<()> {
+            self.stages.borrow_mut().push(RenderStage::Load);
+            Ok(())
+        }
+
+        fn prepare(&mut self) -> Result<()> {
+            self.stages.borrow_mut().push(RenderStage::Prepare);
+            Ok(())
+        }
+
+        fn render(&mut self) -> Result<RgbaImage> {
+            self.stages.borrow_mut().push(RenderStage::Render);
+            match self.fail {
+                true => Err(anyhow!("render failed")),
+                false => Ok(RgbaImage::new(4, 4)),
+            }
+        }
+
+        fn teardown(&mut self) -> Result<()> {
+            self.stages.borrow_mut().push(RenderStage::Teardown);
+            Ok(())
+        }
+    }
+
+    fn svg_files(dir: &TempDir, paths: &[&str]) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for path in paths {
+            let file = dir.join(path);
+            util::io::dir_create_all(file.parent().unwrap())?;
+            std::fs::write(&file, SVG)?;
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    #[test]
+    fn run_goes_through_the_stages_in_order() -> Result<()> {
+        let dir = TempDir::new("rendering-stages");
+        let files = svg_files(&dir, &["a.svg", "b.svg"])?;
+        let stages = Rc::new(RefCell::new(Vec::new()));
+        let hooked = Rc::new(RefCell::new(Vec::new()));
+        let recorder = Recorder {
+            stages: stages.clone(),
+            fail: false,
+        };
+        let hook = hooked.clone();
+        let measurements = RenderingSuite::new(recorder)
+            .files(files)
+            .iterations(2)
+            .on_stage(move |stage, _| hook.borrow_mut().push(stage))
+            .run()?;
+
+        let file =
+            [RenderStage::Load, RenderStage::Prepare, RenderStage::Render];
+        let mut expected = vec![RenderStage::Init];
+        for _ in 0..4 {
+            expected.extend(file);
+        }
+        expected.push(RenderStage::Teardown);
+        assert_eq!(*stages.borrow(), expected);
+        assert_eq!(*hooked.borrow(), expected);
+        let files = measurements
+            .into_iter()
+            .map(|m| m.file)
+            .collect::<Vec<String>>();
+        assert_eq!(files, ["a.svg", "b.svg", "a.svg", "b.svg"]);
+        Ok(())
+    }
+
+    #[test]
+    fn run_tears_down_when_rendering_fails() -> Result<()> {
+        let dir = TempDir::new("rendering-failure");
+        let files = svg_files(&dir, &["a.svg", "b.svg"])?;
+        let stages = Rc::new(RefCell::new(Vec::new()));
+        let recorder = Recorder {
+            stages: stages.clone(),
+            fail: true,
+        };
+        let result = RenderingSuite::new(recorder).files(files).run();
+
+        assert!(result.is_err());
+        assert_eq!(
+            *stages.borrow(),
+            [
+                RenderStage::Init,
+                RenderStage::Load,
+                RenderStage::Prepare,
+                RenderStage::Render,
+                RenderStage::Teardown
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn png_dir_keeps_relative_paths() -> Result<()> {
+        let dir = TempDir::new("rendering-pngs");
+        svg_files(&dir, &["svg/a/shape.svg", "svg/b/shape.svg"])?;
+        let file = svg_files(&dir, &["other/single.svg"])?;
+        let png_dir = dir.join("png");
+        let measurements = RenderingSuite::new(CpuRenderer::default())
+            .dir(dir.join("svg"))
+            .files(file)
+            .png_dir(&png_dir)
+            .run()?;
+
+        assert_eq!(measurements.len(), 3);
+        for png in ["a/shape.png", "b/shape.png", "single.png"] {
+            let image = RgbaImage::read_png(png_dir.join(png))?;
+            assert_eq!(image.pixel(0, 0)[3], 255, "{png}");
+            assert_eq!(image.pixel(3, 3)[3], 0, "{png}");
+        }
+        Ok(())
+    }
+}
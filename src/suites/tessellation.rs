@@ -0,0 +1,129 @@
+use lyon::tessellation::FillOptions;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::macros::measurement;
+use crate::models::{Benchmark, BenchmarkFn, BenchmarkMetadata, Measurements};
+use crate::util;
+use crate::Result;
+
+/// Parse and tessellation cost of one SVG file at one tolerance.
+#[measurement]
+pub struct TessellationMeasurement {
+    pub file: String,
+    pub tolerance: f32,
+    /// Time to parse the file with usvg and convert its paths for lyon.
+    pub parse_time_ns: u128,
+    /// Time to fill and stroke every path with lyon.
+    pub tessellation_time_ns: u128,
+    pub vertices: usize,
+    pub indices: usize,
+}
+
+/// Suite parsing SVG files with usvg and tessellating their fill and stroke
+/// paths with lyon, at one or more tolerances.
+#[derive(Debug, Clone)]
+pub struct TessellationSuite {
+    files: Vec<PathBuf>,
+    tolerances: Vec<f32>,
+    iterations: usize,
+}
+
+impl Default for TessellationSuite {
+    /// Generates a TessellationSuite type with:
+    /// + files: []
+    /// + tolerances: [0.1]
+    /// + iterations: 1
+    fn default() -> Self {
+        TessellationSuite {
+            files: Vec::new(),
+            tolerances: vec![FillOptions::DEFAULT_TOLERANCE],
+            iterations: 1,
+        }
+    }
+}
+
+impl TessellationSuite {
+    pub fn new() -> Self {
+        TessellationSuite::default()
+    }
+
+    /// Add SVG files to the suite.
+    pub fn files<I, P>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.files.extend(files.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add every SVG file under @param dir to the suite, in name order.
+    pub fn dir<P>(self, dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let mut files = util::io::get_files_with_extension(dir, true, "svg");
+        files.sort();
+        self.files(files)
+    }
+
+    /// Select the tolerances files are tessellated at, replacing the default
+    /// of lyon's 0.1.
+    pub fn tolerances<I>(mut self, tolerances: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.tolerances = tolerances.into_iter().collect();
+        self
+    }
+
+    /// Number of times every file is parsed and tessellated.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Parses and tessellates every file, once per iteration, measuring every
+    /// tolerance.
+    pub fn run(&self) -> Result<Measurements<TessellationMeasurement>> {
+        let mut measurements = Measurements::new();
+        for _ in 0..self.iterations {
+            for path in &self.files {
+                let file = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+
+                let start = Instant::now();
+                let tree = util::svg::parse(path)?;
+                let paths = util::svg::paths(&tree);
+                let parse_time_ns = start.elapsed().as_nanos();
+
+                for &tolerance in &self.tolerances {
+                    let start = Instant::now();
+                    let buffers = util::svg::tessellate(&paths, tolerance)?;
+                    let tessellation_time_ns = start.elapsed().as_nanos();
+                    measurements.push(TessellationMeasurement {
+                        file: file.clone(),
+                        tolerance,
+                        parse_time_ns,
+                        tessellation_time_ns,
+                        vertices: buffers.vertices.len(),
+                        indices: buffers.indices.len(),
+                    });
+                }
+            }
+        }
+        Ok(measurements)
+    }
+
+    /// Benchmark named @param name running this suite.
+    pub fn benchmark<S>(self, name: S) -> Benchmark<TessellationMeasurement>
+    where
+        S: Into<String>,
+    {
+        let metadata = BenchmarkMetadata::new(name);
+        Benchmark::new(metadata, BenchmarkFn::new(move || self.run()))
+    }
+}
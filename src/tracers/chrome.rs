@@ -0,0 +1,173 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::Instant;
+
+use crate::models::Tracer;
+use crate::util;
+use crate::Result;
+
+/// Tracer recording every range and mark, written by `flush` to `trace.json`
+/// in the Chrome Trace Event format, for Perfetto or `chrome://tracing`.
+/// Every write only holds the events recorded since the last one.
+#[derive(Debug)]
+pub struct ChromeTracer {
+    start: Instant,
+    state: Mutex<TraceState>,
+}
+
+#[derive(Debug, Default)]
+struct TraceState {
+    events: Vec<TraceEvent>,
+    threads: HashMap<ThreadId, u64>,
+}
+
+/// One event of the Trace Event format, timestamped in microseconds.
+#[derive(Serialize, Debug, Clone)]
+struct TraceEvent {
+    name: String,
+    ph: &'static str,
+    ts: f64,
+    pid: u32,
+    tid: u64,
+    /// Scope of instant events, `t` for the thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<HashMap<&'static str, String>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+impl Default for ChromeTracer {
+    /// Generates a ChromeTracer with no events, timed from now.
+    fn default() -> Self {
+        ChromeTracer {
+            start: Instant::now(),
+            state: Mutex::new(TraceState::default()),
+        }
+    }
+}
+
+impl ChromeTracer {
+    pub fn new() -> Self {
+        ChromeTracer::default()
+    }
+
+    /// Writes every event recorded since the last write to @param path, and
+    /// discards them.
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            util::io::dir_create_all(parent)?;
+        }
+        let mut state = self.state.lock().unwrap();
+        let file = TraceFile {
+            trace_events: &state.events,
+            display_time_unit: "ns",
+        };
+        serde_json::to_writer(BufWriter::new(File::create(path)?), &file)?;
+        // Threads are only named once, so their names are kept for the next
+        state.events.retain(|event| event.ph == "M");
+        Ok(())
+    }
+
+    fn record(&self, name: &str, ph: &'static str) {
+        let ts = self.start.elapsed().as_nanos() as f64 / 1000.0;
+        let pid = std::process::id();
+        let mut state = self.state.lock().unwrap();
+
+        // Threads are numbered as they first trace, and named once
+        let thread = thread::current();
+        let next = state.threads.len() as u64 + 1;
+        let tid = *state.threads.entry(thread.id()).or_insert(next);
+        if tid == next {
+            let name = match thread.name() {
+                Some(name) => name.to_owned(),
+                None => format!("{:?}", thread.id()),
+            };
+            state.events.push(TraceEvent {
+                name: String::from("thread_name"),
+                ph: "M",
+                ts: 0.0,
+                pid,
+                tid,
+                s: None,
+                args: Some(HashMap::from([("name", name)])),
+            });
+        }
+
+        state.events.push(TraceEvent {
+            name: name.to_owned(),
+            ph,
+            ts,
+            pid,
+            tid,
+            s: (ph == "i").then_some("t"),
+            args: None,
+        });
+    }
+}
+
+impl Tracer for ChromeTracer {
+    fn range_push(&self, name: &str) {
+        self.record(name, "B");
+    }
+
+    fn range_pop(&self) {
+        self.record("", "E");
+    }
+
+    fn mark(&self, name: &str) {
+        self.record(name, "i");
+    }
+
+    fn flush(&self, output_dir: &Path) -> Result<()> {
+        self.write(output_dir.join("trace.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    fn names(path: &Path) -> Result<Vec<String>> {
+        let trace: serde_json::Value =
+            serde_json::from_reader(File::open(path)?)?;
+        Ok(trace["traceEvents"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|event| event["name"].as_str().unwrap_or_default().to_owned())
+            .collect())
+    }
+
+    #[test]
+    fn flush_writes_events_since_the_last_flush() -> Result<()> {
+        let dir = TempDir::new("chrome");
+        let tracer = ChromeTracer::new();
+        tracer.mark("first");
+        tracer.flush(&dir.join("1"))?;
+        tracer.mark("second");
+        tracer.flush(&dir.join("2"))?;
+        let first = names(&dir.join("1/trace.json"))?;
+        let second = names(&dir.join("2/trace.json"))?;
+
+        assert_eq!(first, ["thread_name", "first"]);
+        assert_eq!(second, ["thread_name", "second"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! Tracer implementations and the active tracer of the process.
+
+use std::sync::{Arc, RwLock};
+
+use crate::models::Tracer;
+
+mod chrome;
+pub use chrome::ChromeTracer;
+
+mod noop;
+pub use noop::NoopTracer;
+
+#[cfg(feature = "nvtx")]
+mod nvtx;
+#[cfg(feature = "nvtx")]
+pub use self::nvtx::NvtxTracer;
+
+static ACTIVE: RwLock<Option<Arc<dyn Tracer>>> = RwLock::new(None);
+
+/// The tracer ranges and marks are sent to: the last one set, or else an
+/// NvtxTracer when built with the `nvtx` feature and a NoopTracer otherwise.
+pub fn active() -> Arc<dyn Tracer> {
+    if let Some(tracer) = ACTIVE.read().unwrap().as_ref() {
+        return tracer.clone();
+    }
+    ACTIVE.write().unwrap().get_or_insert_with(default).clone()
+}
+
+/// Send every range and mark to @param tracer from now on, returning the
+/// tracer set before, if any.
+pub fn set_active(tracer: Arc<dyn Tracer>) -> Option<Arc<dyn Tracer>> {
+    ACTIVE.write().unwrap().replace(tracer)
+}
+
+/// Send every range and mark to @param tracer until the returned guard is
+/// dropped, which restores the tracer set before.
+pub fn activate(tracer: Arc<dyn Tracer>) -> ActiveGuard {
+    ActiveGuard {
+        previous: set_active(tracer),
+    }
+}
+
+/// Guard restoring the previously active tracer when dropped.
+#[must_use = "the tracer is deactivated when the guard is dropped"]
+pub struct ActiveGuard {
+    previous: Option<Arc<dyn Tracer>>,
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        *ACTIVE.write().unwrap() = self.previous.take();
+    }
+}
+
+fn default() -> Arc<dyn Tracer> {
+    #[cfg(feature = "nvtx")]
+    return Arc::new(NvtxTracer);
+    #[cfg(not(feature = "nvtx"))]
+    return Arc::new(NoopTracer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_active(tracer: &Arc<dyn Tracer>) -> bool {
+        let active = ACTIVE.read().unwrap().clone();
+        active.is_some_and(|active| {
+            Arc::as_ptr(&active) as *const ()
+                == Arc::as_ptr(tracer) as *const ()
+        })
+    }
+
+    #[test]
+    fn guards_restore_the_previous_tracer() {
+        let outer: Arc<dyn Tracer> = Arc::new(NoopTracer);
+        let inner: Arc<dyn Tracer> = Arc::new(NoopTracer);
+
+        let outer_guard = activate(outer.clone());
+        {
+            let _inner_guard = activate(inner.clone());
+            assert!(is_active(&inner));
+        }
+        assert!(is_active(&outer));
+        drop(outer_guard);
+        assert!(!is_active(&outer));
+    }
+}
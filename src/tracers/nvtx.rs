@@ -0,0 +1,19 @@
+use crate::models::Tracer;
+
+/// Tracer forwarding ranges and marks to NVTX, for Nsight Systems.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NvtxTracer;
+
+impl Tracer for NvtxTracer {
+    fn range_push(&self, name: &str) {
+        ::nvtx::range_push(name);
+    }
+
+    fn range_pop(&self) {
+        ::nvtx::range_pop();
+    }
+
+    fn mark(&self, name: &str) {
+        ::nvtx::mark(name);
+    }
+}
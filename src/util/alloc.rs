@@ -0,0 +1,111 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static DEALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Global allocator forwarding to the System allocator while counting the
+/// allocations of every thread. It must be installed by the binary, which
+/// the vgpu-bench binary does with the `alloc-counting` feature:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    // Counters are unavailable while the thread is being torn down
+    let _ = ALLOCATED.try_with(|c| c.set(c.get() + size as u64));
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+}
+
+fn record_dealloc(size: usize) {
+    let _ = DEALLOCATED.try_with(|c| c.set(c.get() + size as u64));
+}
+
+/// Whether a CountingAllocator is the global allocator, i.e. whether
+/// AllocationStats are meaningful.
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Allocation counters of the current thread, cumulative since it started.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllocationStats {
+    pub allocated_bytes: u64,
+    pub deallocated_bytes: u64,
+    pub allocations: u64,
+}
+
+impl AllocationStats {
+    /// Snapshot of the current thread's counters.
+    pub fn current() -> Self {
+        AllocationStats {
+            allocated_bytes: ALLOCATED.with(Cell::get),
+            deallocated_bytes: DEALLOCATED.with(Cell::get),
+            allocations: ALLOCATIONS.with(Cell::get),
+        }
+    }
+
+    /// Counters accumulated between the snapshot @param earlier and this one.
+    pub fn since(&self, earlier: &AllocationStats) -> AllocationStats {
+        AllocationStats {
+            allocated_bytes: self.allocated_bytes - earlier.allocated_bytes,
+            deallocated_bytes: self.deallocated_bytes
+                - earlier.deallocated_bytes,
+            allocations: self.allocations - earlier.allocations,
+        }
+    }
+
+    /// Bytes allocated and not yet deallocated.
+    pub fn retained_bytes(&self) -> u64 {
+        self.allocated_bytes.saturating_sub(self.deallocated_bytes)
+    }
+}
@@ -1,9 +1,9 @@
 use crate::Result;
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use log::{error, trace};
 use std::{
     ffi::{OsStr, OsString},
-    process::Output,
+    process::{Command, Output, Stdio},
 };
 
 pub fn call_program<I, S>(program_path: S, args: I) -> Result<Output>
@@ -25,16 +25,10 @@ where
     );
 
     // Run program
-    let output = std::process::Command::new(&program_path)
+    let output = Command::new(&program_path)
         .args(args)
         .output()
-        .expect(
-            format!(
-                "'{}' was unable to execute, is it in your PATH?",
-                program_path.to_string_lossy()
-            )
-            .as_str(),
-        );
+        .map_err(|e| unable_to_execute(&program_path, e))?;
 
     // Check status code
     let output = match output.status.success() {
@@ -61,3 +55,42 @@ where
     );
     Ok(output)
 }
+
+/// Runs the program at @param program_path with @param args, sharing the
+/// standard streams of this process, e.g. for long running programs whose
+/// progress should be seen.
+pub fn run_program<I, S>(program_path: S, args: I) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let program_path = OsString::from(program_path.as_ref());
+    trace!("executing process '{}'", program_path.to_string_lossy());
+
+    let status = Command::new(&program_path)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| unable_to_execute(&program_path, e))?;
+    if !status.success() {
+        bail!(
+            "'{}' exited with failure ({status})",
+            program_path.to_string_lossy()
+        );
+    }
+
+    trace!(
+        "completed program '{}' successfully",
+        program_path.to_string_lossy()
+    );
+    Ok(())
+}
+
+fn unable_to_execute(program_path: &OsStr, e: std::io::Error) -> crate::Error {
+    anyhow!(
+        "'{}' was unable to execute ({e}), is it in your PATH?",
+        program_path.to_string_lossy()
+    )
+}
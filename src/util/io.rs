@@ -91,6 +91,19 @@ where
     Ok(())
 }
 
+/// Remove a file if it exists, ensuring it no longer exists afterwards.
+pub fn file_remove_if_exists<P>(path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    if path.exists() {
+        std::fs::remove_file(path)?;
+        ensure!(!path.exists(), "{path:?} could not be removed");
+    }
+    Ok(())
+}
+
 pub fn create_or_append<P>(path: P) -> Result<File>
 where
     P: AsRef<Path>,
@@ -1,4 +1,8 @@
+pub mod alloc;
 pub mod convert;
 pub mod exec;
 pub mod io;
 pub mod logging;
+pub mod svg;
+#[cfg(test)]
+pub(crate) mod testing;
@@ -0,0 +1,238 @@
+use anyhow::anyhow;
+use lyon::math::{point, Point};
+use lyon::path::Path;
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex,
+    LineCap, LineJoin, StrokeOptions, StrokeTessellator, StrokeVertex,
+    VertexBuffers,
+};
+use std::path::Path as FilePath;
+use usvg::{NodeExt, NodeKind, PathData, PathSegment, Transform, Tree};
+
+use crate::Result;
+
+/// Absolute directory of the bundled primitive SVGs.
+pub const PRIMITIVES_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/assets/svg/primitives");
+
+/// Absolute directory of the bundled example SVGs.
+pub const EXAMPLES_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/assets/svg/examples");
+
+/// A visible path of an SVG document, flattened into document coordinates.
+pub struct SvgPath {
+    pub path: Path,
+    /// Fill rule of the path, if it is filled.
+    pub fill: Option<FillRule>,
+    /// Stroke of the path, with its width in document coordinates, if it is
+    /// stroked. Dash arrays are ignored.
+    pub stroke: Option<StrokeOptions>,
+}
+
+/// Parses the SVG file at @param path into a usvg Tree.
+pub fn parse<P>(path: P) -> Result<Tree>
+where
+    P: AsRef<FilePath>,
+{
+    let path = path.as_ref();
+    let data = std::fs::read(path)?;
+    Tree::from_data(&data, &usvg::Options::default().to_ref())
+        .map_err(|e| anyhow!("{path:?}: failed to parse with error '{e}'"))
+}
+
+/// Every visible path of @param tree, outside of its definitions, with its
+/// absolute transform applied.
+pub fn paths(tree: &Tree) -> Vec<SvgPath> {
+    let mut paths = Vec::new();
+    for node in tree.root().descendants() {
+        if tree.is_in_defs(&node) {
+            continue;
+        }
+        if let NodeKind::Path(ref path) = *node.borrow() {
+            if path.visibility != usvg::Visibility::Visible {
+                continue;
+            }
+            let transform = node.abs_transform();
+            let scale = transform.get_scale();
+            let stroke = path.stroke.as_ref().map(|stroke| {
+                // Widths scale with the mean of the axes' scale factors
+                let width = stroke.width.value() * (scale.0 + scale.1) / 2.0;
+                stroke_options(stroke, width as f32)
+            });
+            paths.push(SvgPath {
+                path: to_lyon_path(&path.data, &transform),
+                fill: path.fill.as_ref().map(|fill| fill_rule(fill.rule)),
+                stroke,
+            });
+        }
+    }
+    paths
+}
+
+/// Converts a usvg fill rule into its lyon equivalent.
+pub fn fill_rule(rule: usvg::FillRule) -> FillRule {
+    match rule {
+        usvg::FillRule::NonZero => FillRule::NonZero,
+        usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+    }
+}
+
+/// Converts the caps, joins and miter limit of a usvg @param stroke into
+/// lyon StrokeOptions @param width wide. Dash arrays are ignored.
+pub fn stroke_options(stroke: &usvg::Stroke, width: f32) -> StrokeOptions {
+    let cap = match stroke.linecap {
+        usvg::LineCap::Butt => LineCap::Butt,
+        usvg::LineCap::Round => LineCap::Round,
+        usvg::LineCap::Square => LineCap::Square,
+    };
+    let join = match stroke.linejoin {
+        usvg::LineJoin::Miter => LineJoin::Miter,
+        usvg::LineJoin::Round => LineJoin::Round,
+        usvg::LineJoin::Bevel => LineJoin::Bevel,
+    };
+    // usvg guarantees limits of at least 1, as lyon requires
+    StrokeOptions::default()
+        .with_line_width(width)
+        .with_line_cap(cap)
+        .with_line_join(join)
+        .with_miter_limit(stroke.miterlimit.value() as f32)
+}
+
+/// Converts usvg path data into a lyon Path, applying @param transform.
+pub fn to_lyon_path(data: &PathData, transform: &Transform) -> Path {
+    let to_point = |x: f64, y: f64| {
+        let (x, y) = transform.apply(x, y);
+        point(x as f32, y as f32)
+    };
+
+    let mut builder = Path::builder();
+    let mut open = false;
+    for segment in data.iter() {
+        match *segment {
+            PathSegment::MoveTo { x, y } => {
+                if open {
+                    builder.end(false);
+                }
+                builder.begin(to_point(x, y));
+                open = true;
+            }
+            PathSegment::LineTo { x, y } if open => {
+                builder.line_to(to_point(x, y));
+            }
+            PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } if open => {
+                builder.cubic_bezier_to(
+                    to_point(x1, y1),
+                    to_point(x2, y2),
+                    to_point(x, y),
+                );
+            }
+            PathSegment::ClosePath if open => {
+                builder.end(true);
+                open = false;
+            }
+            // Drawing commands without a current point are malformed
+            _ => {}
+        }
+    }
+    if open {
+        builder.end(false);
+    }
+    builder.build()
+}
+
+/// Tessellates the fills and strokes of @param paths into one triangle list,
+/// approximating curves to within @param tolerance.
+pub fn tessellate(
+    paths: &[SvgPath],
+    tolerance: f32,
+) -> Result<VertexBuffers<Point, u32>> {
+    let mut buffers = VertexBuffers::new();
+    let mut fill_tessellator = FillTessellator::new();
+    let mut stroke_tessellator = StrokeTessellator::new();
+    for svg_path in paths {
+        if let Some(rule) = svg_path.fill {
+            let fill_options =
+                FillOptions::tolerance(tolerance).with_fill_rule(rule);
+            fill_tessellator
+                .tessellate_path(
+                    &svg_path.path,
+                    &fill_options,
+                    &mut BuffersBuilder::new(&mut buffers, |v: FillVertex| {
+                        v.position()
+                    }),
+                )
+                .map_err(|e| anyhow!("fill tessellation failed: {e:?}"))?;
+        }
+        if let Some(stroke) = svg_path.stroke {
+            let stroke_options = stroke.with_tolerance(tolerance);
+            stroke_tessellator
+                .tessellate_path(
+                    &svg_path.path,
+                    &stroke_options,
+                    &mut BuffersBuilder::new(
+                        &mut buffers,
+                        |v: StrokeVertex| v.position(),
+                    ),
+                )
+                .map_err(|e| anyhow!("stroke tessellation failed: {e:?}"))?;
+        }
+    }
+    Ok(buffers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(svg: &str) -> Tree {
+        Tree::from_data(svg.as_bytes(), &usvg::Options::default().to_ref())
+            .unwrap()
+    }
+
+    #[test]
+    fn paths_keep_fill_rules_and_stroke_styles() {
+        let tree = tree(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <path d="M0 0 H8 V8 Z" fill-rule="evenodd"/>
+                <path d="M0 0 L8 8" fill="none" stroke="black"
+                    stroke-width="2" stroke-linecap="round"
+                    stroke-linejoin="bevel" stroke-miterlimit="6"/>
+            </svg>"#,
+        );
+        let paths = paths(&tree);
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].fill, Some(FillRule::EvenOdd));
+        assert!(paths[0].stroke.is_none());
+        assert!(paths[1].fill.is_none());
+        let stroke = paths[1].stroke.unwrap();
+        assert_eq!(stroke.line_width, 2.0);
+        assert_eq!(stroke.start_cap, LineCap::Round);
+        assert_eq!(stroke.end_cap, LineCap::Round);
+        assert_eq!(stroke.line_join, LineJoin::Bevel);
+        assert_eq!(stroke.miter_limit, 6.0);
+    }
+
+    #[test]
+    fn round_caps_add_vertices() -> Result<()> {
+        let stroke = |cap: &str| {
+            tree(&format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="10"
+                    height="10"><path d="M1 5 L9 5" stroke="black"
+                    stroke-width="2" stroke-linecap="{cap}"/></svg>"#
+            ))
+        };
+        let butt = tessellate(&paths(&stroke("butt")), 0.01)?;
+        let round = tessellate(&paths(&stroke("round")), 0.01)?;
+
+        assert!(round.vertices.len() > butt.vertices.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,437 @@
+use anyhow::{anyhow, ensure};
+use arrow::array::{
+    ArrayRef, BooleanBuilder, Float64Builder, Int64Builder, NullArray,
+    StringBuilder,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use log::{trace, warn};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::models::{
+    Column, FieldType, Measurable, Measurements, Table, Value, Writer,
+};
+use crate::util;
+use crate::Result;
+
+/// Arrow type of the column of fields serialized as @param field_type.
+/// Columns of fields that are `None` in every row are typed as nulls until
+/// values are appended to them.
+fn data_type(field_type: FieldType) -> DataType {
+    match field_type {
+        FieldType::Null => DataType::Null,
+        FieldType::Bool => DataType::Boolean,
+        FieldType::Int => DataType::Int64,
+        FieldType::Float => DataType::Float64,
+        FieldType::Str => DataType::Utf8,
+    }
+}
+
+/// Tabulates @param data with a schema derived from the types its fields
+/// are serialized as, rather than from their values. Every field is
+/// nullable.
+fn tabulate<T>(data: &Measurements<T>) -> Result<(Table, SchemaRef)>
+where
+    T: Measurable,
+{
+    let (table, types) = Table::from_measurements_typed(data)?;
+    let fields = table
+        .columns()
+        .iter()
+        .zip(types)
+        .map(|(c, t)| Field::new(c.name(), data_type(t), true))
+        .collect::<Vec<Field>>();
+    Ok((table, Arc::new(Schema::new(fields))))
+}
+
+/// Converts @param value to the type of @param field, which fails for
+/// values other than null that do not fit it.
+fn fit<T, F>(value: &Value, field: &Field, convert: F) -> Result<Option<T>>
+where
+    F: Fn(&Value) -> Option<T>,
+{
+    match value.is_null() {
+        true => Ok(None),
+        false => convert(value).map(Some).ok_or_else(|| {
+            anyhow!(
+                "column '{}' of type {} cannot hold {value:?}",
+                field.name(),
+                field.data_type()
+            )
+        }),
+    }
+}
+
+fn array(
+    column: Option<&Column>,
+    rows: usize,
+    field: &Field,
+) -> Result<ArrayRef> {
+    let null = Value::Null;
+    let values = (0..rows).map(|i| column.map_or(&null, |c| &c.values()[i]));
+    let array: ArrayRef = match field.data_type() {
+        DataType::Null => {
+            for value in values {
+                fit(value, field, |_| None::<()>)?;
+            }
+            Arc::new(NullArray::new(rows))
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(rows);
+            for value in values {
+                builder.append_option(fit(value, field, Value::as_i64)?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(rows);
+            for value in values {
+                builder.append_option(fit(value, field, Value::as_f64)?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(rows);
+            for value in values {
+                builder.append_option(fit(value, field, Value::as_bool)?);
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            values.for_each(|v| match v.is_null() {
+                true => builder.append_null(),
+                false => builder.append_value(v.to_string()),
+            });
+            Arc::new(builder.finish())
+        }
+    };
+    Ok(array)
+}
+
+/// Converts a table into a RecordBatch of the given schema. Values which do
+/// not fit the type of their field are an error rather than lost.
+fn record_batch(table: &Table, schema: SchemaRef) -> Result<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|f| array(table.column(f.name()), table.len(), f))
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// Reads the existing batches of @param path and appends the rows of
+/// @param data with the same schema.
+fn appended<T, F>(
+    data: &Measurements<T>,
+    path: &Path,
+    read: F,
+) -> Result<(SchemaRef, Vec<RecordBatch>)>
+where
+    T: Measurable,
+    F: FnOnce(File) -> Result<(SchemaRef, Vec<RecordBatch>)>,
+{
+    let (table, appended) = tabulate(data)?;
+    let (schema, mut batches) = match path.is_file() {
+        true => {
+            let (schema, batches) = read(File::open(path)?)?;
+            ensure!(
+                appended
+                    .fields()
+                    .iter()
+                    .all(|f| schema.field_with_name(f.name()).is_ok()),
+                "{path:?} schema does not match the appended measurements"
+            );
+            typed(schema, batches, &appended)?
+        }
+        false => (appended, Vec::new()),
+    };
+    batches.push(record_batch(&table, schema.clone())?);
+    Ok((schema, batches))
+}
+
+/// Gives the null fields of @param schema the types of the same fields in
+/// @param appended, casting @param batches to match.
+fn typed(
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    appended: &Schema,
+) -> Result<(SchemaRef, Vec<RecordBatch>)> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| match appended.field_with_name(field.name()) {
+            Ok(other) if field.data_type() == &DataType::Null => other.clone(),
+            _ => field.as_ref().clone(),
+        })
+        .collect::<Vec<Field>>();
+    let typed = Arc::new(Schema::new(fields));
+    if typed.fields() == schema.fields() {
+        return Ok((schema, batches));
+    }
+    let batches = batches
+        .iter()
+        .map(|batch| {
+            let columns = batch
+                .columns()
+                .iter()
+                .zip(typed.fields())
+                .map(|(column, field)| Ok(cast(column, field.data_type())?))
+                .collect::<Result<Vec<ArrayRef>>>()?;
+            Ok(RecordBatch::try_new(typed.clone(), columns)?)
+        })
+        .collect::<Result<Vec<RecordBatch>>>()?;
+    Ok((typed, batches))
+}
+
+/// Writes Measurements as an Apache Parquet file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParquetWriter;
+
+impl ParquetWriter {
+    fn read(file: File) -> Result<(SchemaRef, Vec<RecordBatch>)> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+        let schema = builder.schema().clone();
+        let batches = builder.build()?.collect::<Result<Vec<_>, _>>()?;
+        Ok((schema, batches))
+    }
+
+    fn write_batches(
+        path: &Path,
+        schema: SchemaRef,
+        batches: &[RecordBatch],
+    ) -> Result<()> {
+        let file = util::io::create_or_append(path)?;
+        let mut writer = ArrowWriter::try_new(file, schema, None)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.close()?;
+        Ok(())
+    }
+}
+
+impl Writer for ParquetWriter {
+    /// Parquet files are immutable, so appending rewrites the file with the
+    /// existing row groups followed by the new rows.
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("parquet");
+
+        trace!("appending measurements to {path:?}");
+
+        let (schema, batches) = appended(data, &path, ParquetWriter::read)?;
+        util::io::file_remove_if_exists(&path)?;
+        ParquetWriter::write_batches(&path, schema, &batches)
+    }
+
+    /// Writes a Parquet file to @param path, overwriting any previous file.
+    /// If Measurements is empty, nothing is written.
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("parquet");
+
+        trace!("writing measurements to {path:?}");
+
+        util::io::file_remove_if_exists(&path)?;
+        if data.is_empty() {
+            warn!("{path:?} no measurable to write, skipping");
+            return Ok(());
+        }
+        let (table, schema) = tabulate(data)?;
+        let batch = record_batch(&table, schema.clone())?;
+        ParquetWriter::write_batches(&path, schema, &[batch])
+    }
+}
+
+/// Writes Measurements as an Arrow IPC (Feather v2) file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArrowIpcWriter;
+
+impl ArrowIpcWriter {
+    fn read(file: File) -> Result<(SchemaRef, Vec<RecordBatch>)> {
+        let reader = arrow::ipc::reader::FileReader::try_new(file, None)?;
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        Ok((schema, batches))
+    }
+
+    fn write_batches(
+        path: &Path,
+        schema: SchemaRef,
+        batches: &[RecordBatch],
+    ) -> Result<()> {
+        let file = util::io::create_or_append(path)?;
+        let mut writer =
+            arrow::ipc::writer::FileWriter::try_new(file, &schema)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+impl Writer for ArrowIpcWriter {
+    /// Arrow IPC files end with a footer, so appending rewrites the file with
+    /// the existing batches followed by the new rows.
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("arrow");
+
+        trace!("appending measurements to {path:?}");
+
+        let (schema, batches) = appended(data, &path, ArrowIpcWriter::read)?;
+        util::io::file_remove_if_exists(&path)?;
+        ArrowIpcWriter::write_batches(&path, schema, &batches)
+    }
+
+    /// Writes an Arrow IPC file to @param path, overwriting any previous
+    /// file. If Measurements is empty, nothing is written.
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("arrow");
+
+        trace!("writing measurements to {path:?}");
+
+        util::io::file_remove_if_exists(&path)?;
+        if data.is_empty() {
+            warn!("{path:?} no measurable to write, skipping");
+            return Ok(());
+        }
+        let (table, schema) = tabulate(data)?;
+        let batch = record_batch(&table, schema.clone())?;
+        ArrowIpcWriter::write_batches(&path, schema, &[batch])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    #[derive(Serialize, Debug)]
+    struct Frame {
+        frame: u32,
+        time: f64,
+    }
+
+    #[derive(Serialize, Debug)]
+    struct Labelled {
+        frame: &'static str,
+        time: Option<f64>,
+    }
+
+    #[test]
+    fn append_rejects_values_not_fitting_the_schema() -> Result<()> {
+        let dir = TempDir::new("columnar");
+        let path = dir.join("measurements");
+
+        let frames = [Frame {
+            frame: 1,
+            time: 2.5,
+        }];
+        ParquetWriter.write(&frames.into_iter().collect(), &path)?;
+        let nulls = [Labelled {
+            frame: "2",
+            time: None,
+        }];
+        let result = ParquetWriter.append(&nulls.into_iter().collect(), &path);
+        let frames = [Frame {
+            frame: 3,
+            time: 4.0,
+        }];
+        ParquetWriter.append(&frames.into_iter().collect(), &path)?;
+        let file = File::open(path.with_extension("parquet"))?;
+        let (schema, batches) = ParquetWriter::read(file)?;
+
+        let error = result.expect_err("a string does not fit an Int64 column");
+        assert!(error.to_string().contains("column 'frame'"), "{error}");
+        assert_eq!(
+            schema.field_with_name("frame")?.data_type(),
+            &DataType::Int64
+        );
+        let rows = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+        assert_eq!(rows, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn schema_follows_the_field_types() -> Result<()> {
+        let nulls = [Labelled {
+            frame: "1",
+            time: None,
+        }];
+        let (_, schema) = tabulate(&nulls.into_iter().collect())?;
+        assert_eq!(
+            schema.field_with_name("time")?.data_type(),
+            &DataType::Null
+        );
+
+        let times = [Labelled {
+            frame: "1",
+            time: Some(2.0),
+        }];
+        let (_, schema) = tabulate(&times.into_iter().collect())?;
+        assert_eq!(
+            schema.field_with_name("time")?.data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(
+            schema.field_with_name("frame")?.data_type(),
+            &DataType::Utf8
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn append_types_null_columns() -> Result<()> {
+        let dir = TempDir::new("columnar-nulls");
+        let path = dir.join("measurements");
+
+        let nulls = [Labelled {
+            frame: "1",
+            time: None,
+        }];
+        let times = [Labelled {
+            frame: "2",
+            time: Some(3.5),
+        }];
+        ParquetWriter.write(&nulls.into_iter().collect(), &path)?;
+        ParquetWriter.append(&times.into_iter().collect(), &path)?;
+        let file = File::open(path.with_extension("parquet"))?;
+        let (schema, batches) = ParquetWriter::read(file)?;
+
+        assert_eq!(
+            schema.field_with_name("time")?.data_type(),
+            &DataType::Float64
+        );
+        let rows = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+        assert_eq!(rows, 2);
+        assert_eq!(batches[0].column(1).null_count(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use log::{trace, warn};
+use std::path::Path;
+
+use crate::models::{Measurable, Measurements, Table, Writer};
+use crate::util;
+use crate::Result;
+
+/// Writes Measurements as comma-separated values with a header row.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvWriter;
+
+impl Writer for CsvWriter {
+    /// Appends rows to the CSV at @param path, writing a header row only if
+    /// the file is new. Rows are aligned to the columns of an existing header,
+    /// leaving the fields of columns the data lacks empty.
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("csv");
+
+        trace!("appending measurements to {path:?}");
+
+        let mut table = Table::from_measurements(data)?;
+        let file = util::io::create_or_append(&path)?;
+        let is_new = file.metadata()?.len() == 0;
+        if !is_new {
+            let header = csv::Reader::from_path(&path)?.headers()?.clone();
+            let columns = header.iter().collect::<Vec<&str>>();
+            if table.column_names().any(|name| !columns.contains(&name)) {
+                warn!("{path:?} dropping columns missing from its header");
+            }
+            table = table.conform(&columns);
+        }
+        table.write_csv(file, is_new)
+    }
+
+    /// Writes a CSV to @param path, overwriting any previous file. If
+    /// Measurements is empty, nothing is written.
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("csv");
+
+        trace!("writing measurements to {path:?}");
+
+        util::io::file_remove_if_exists(&path)?;
+        if data.is_empty() {
+            warn!("{path:?} no measurable to write, skipping");
+        } else {
+            let table = Table::from_measurements(data)?;
+            table.write_csv(util::io::create_or_append(path)?, true)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::util::testing::TempDir;
+
+    #[derive(Serialize, Debug)]
+    struct Full {
+        #[serde(rename = "time, ns")]
+        time: u32,
+        name: &'static str,
+        frames: u32,
+    }
+
+    #[derive(Serialize, Debug)]
+    struct Partial {
+        frames: u32,
+        #[serde(rename = "time, ns")]
+        time: u32,
+        extra: u32,
+    }
+
+    #[test]
+    fn append_aligns_rows_to_quoted_header() -> Result<()> {
+        let dir = TempDir::new("csv-append");
+        let path = dir.join("measurements");
+
+        let full = [Full {
+            time: 1,
+            name: "a",
+            frames: 2,
+        }];
+        CsvWriter.write(&full.into_iter().collect(), &path)?;
+        let partial = [Partial {
+            frames: 4,
+            time: 3,
+            extra: 5,
+        }];
+        CsvWriter.append(&partial.into_iter().collect(), &path)?;
+
+        let written = std::fs::read_to_string(path.with_extension("csv"))?;
+        assert_eq!(written, "\"time, ns\",name,frames\n1,a,2\n3,,4\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use log::{trace, warn};
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::models::{Measurable, Measurements, Writer};
+use crate::util;
+use crate::Result;
+
+/// Writes Measurements as a single pretty-printed JSON array.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonWriter;
+
+impl Writer for JsonWriter {
+    /// Extends the JSON array at @param path. The existing array must be
+    /// read and rewritten, so prefer `JsonLinesWriter` for frequent appends.
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("json");
+
+        trace!("appending measurements to {path:?}");
+
+        let mut values = match path.is_file() {
+            true => {
+                let file = std::fs::File::open(&path)?;
+                serde_json::from_reader::<_, Vec<serde_json::Value>>(file)?
+            }
+            false => Vec::new(),
+        };
+        for measurable in data {
+            values.push(serde_json::to_value(measurable)?);
+        }
+        util::io::file_remove_if_exists(&path)?;
+        let file = util::io::create_or_append(&path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &values)?;
+        Ok(())
+    }
+
+    /// Writes a JSON array to @param path, overwriting any previous file. If
+    /// Measurements is empty, nothing is written.
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("json");
+
+        trace!("writing measurements to {path:?}");
+
+        util::io::file_remove_if_exists(&path)?;
+        if data.is_empty() {
+            warn!("{path:?} no measurable to write, skipping");
+        } else {
+            let file = util::io::create_or_append(&path)?;
+            let measurables = data.iter().collect::<Vec<&T>>();
+            serde_json::to_writer_pretty(BufWriter::new(file), &measurables)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use log::{trace, warn};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::models::{Measurable, Measurements, Writer};
+use crate::util;
+use crate::Result;
+
+/// Writes Measurements as JSON Lines, one JSON object per line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonLinesWriter;
+
+impl JsonLinesWriter {
+    fn write_lines<T, P>(data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut writer = BufWriter::new(util::io::create_or_append(path)?);
+        for measurable in data {
+            serde_json::to_writer(&mut writer, measurable)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Writer for JsonLinesWriter {
+    /// Appends one line per Measurable to @param path.
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("jsonl");
+
+        trace!("appending measurements to {path:?}");
+
+        JsonLinesWriter::write_lines(data, path)
+    }
+
+    /// Writes JSON Lines to @param path, overwriting any previous file. If
+    /// Measurements is empty, nothing is written.
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        let mut path = path.as_ref().to_owned();
+        path.set_extension("jsonl");
+
+        trace!("writing measurements to {path:?}");
+
+        util::io::file_remove_if_exists(&path)?;
+        if data.is_empty() {
+            warn!("{path:?} no measurable to write, skipping");
+            Ok(())
+        } else {
+            JsonLinesWriter::write_lines(data, path)
+        }
+    }
+}
@@ -0,0 +1,23 @@
+mod csv;
+pub use self::csv::CsvWriter;
+
+mod json;
+pub use json::JsonWriter;
+
+mod json_lines;
+pub use json_lines::JsonLinesWriter;
+
+#[cfg(feature = "parquet")]
+mod columnar;
+#[cfg(feature = "parquet")]
+pub use columnar::ArrowIpcWriter;
+#[cfg(feature = "parquet")]
+pub use columnar::ParquetWriter;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDatabase;
+
+mod output_format;
+pub use output_format::OutputFormat;
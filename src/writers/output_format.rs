@@ -0,0 +1,120 @@
+use anyhow::anyhow;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::models::{Measurable, Measurements, Writer};
+#[cfg(feature = "parquet")]
+use crate::writers::{ArrowIpcWriter, ParquetWriter};
+use crate::writers::{CsvWriter, JsonLinesWriter, JsonWriter};
+use crate::Result;
+
+/// Selectable output format, dispatching to the Writer of that format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    JsonLines,
+    #[cfg(feature = "parquet")]
+    Parquet,
+    #[cfg(feature = "parquet")]
+    ArrowIpc,
+}
+
+impl Default for OutputFormat {
+    /// Generates a Default State of Csv.
+    fn default() -> Self {
+        OutputFormat::Csv
+    }
+}
+
+impl OutputFormat {
+    /// File extension written by this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::JsonLines => "jsonl",
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => "parquet",
+            #[cfg(feature = "parquet")]
+            OutputFormat::ArrowIpc => "arrow",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = crate::Error;
+
+    /// Parses a format by name or file extension, e.g. `csv` or `jsonl`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" | "json-lines" => Ok(OutputFormat::JsonLines),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(OutputFormat::Parquet),
+            #[cfg(feature = "parquet")]
+            "arrow" | "arrow-ipc" => Ok(OutputFormat::ArrowIpc),
+            _ => Err(anyhow!("unknown output format '{s}'")),
+        }
+    }
+}
+
+impl Writer for OutputFormat {
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        match self {
+            OutputFormat::Csv => CsvWriter.append(data, path),
+            OutputFormat::Json => JsonWriter.append(data, path),
+            OutputFormat::JsonLines => JsonLinesWriter.append(data, path),
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => ParquetWriter.append(data, path),
+            #[cfg(feature = "parquet")]
+            OutputFormat::ArrowIpc => ArrowIpcWriter.append(data, path),
+        }
+    }
+
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        match self {
+            OutputFormat::Csv => CsvWriter.write(data, path),
+            OutputFormat::Json => JsonWriter.write(data, path),
+            OutputFormat::JsonLines => JsonLinesWriter.write(data, path),
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => ParquetWriter.write(data, path),
+            #[cfg(feature = "parquet")]
+            OutputFormat::ArrowIpc => ArrowIpcWriter.write(data, path),
+        }
+    }
+}
+
+/// Writes with every selected format in turn.
+impl Writer for [OutputFormat] {
+    fn append<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        for format in self {
+            format.append(data, path.as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn write<T, P>(&self, data: &Measurements<T>, path: P) -> Result<()>
+    where
+        T: Measurable,
+        P: AsRef<Path>,
+    {
+        for format in self {
+            format.write(data, path.as_ref())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+use log::{debug, trace};
+use rusqlite::types::{ToSqlOutput, Value as SqlValue};
+use rusqlite::{params, Connection, ToSql, Transaction};
+use std::path::Path;
+
+use crate::analysis::BenchmarkSummary;
+use crate::models::{DriverBundle, Measurable, Measurements, Table, Value};
+use crate::Result;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp   INTEGER NOT NULL,
+    hostname    TEXT NOT NULL,
+    os          TEXT NOT NULL,
+    family      TEXT NOT NULL,
+    arch        TEXT NOT NULL,
+    cpus        INTEGER NOT NULL,
+    version     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS benchmarks (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id      INTEGER NOT NULL REFERENCES runs(id),
+    name        TEXT NOT NULL,
+    status      TEXT NOT NULL,
+    duration_ns INTEGER,
+    error       TEXT,
+    monitors    TEXT,
+    UNIQUE (run_id, name)
+);
+CREATE TABLE IF NOT EXISTS measurements (
+    benchmark_id INTEGER NOT NULL REFERENCES benchmarks(id),
+    row          INTEGER NOT NULL,
+    metric       TEXT NOT NULL,
+    value
+);
+CREATE TABLE IF NOT EXISTS monitor_samples (
+    benchmark_id INTEGER NOT NULL REFERENCES benchmarks(id),
+    monitor      TEXT NOT NULL,
+    row          INTEGER NOT NULL,
+    metric       TEXT NOT NULL,
+    value
+);
+CREATE INDEX IF NOT EXISTS measurements_by_metric
+    ON measurements (benchmark_id, metric);
+CREATE INDEX IF NOT EXISTS monitor_samples_by_metric
+    ON monitor_samples (benchmark_id, monitor, metric);
+";
+
+/// Columns of the `benchmarks` table added since the first version of the
+/// schema, which are added to older databases when they are opened.
+const ADDED_BENCHMARK_COLUMNS: [(&str, &str); 2] =
+    [("duration_ns", "INTEGER"), ("monitors", "TEXT")];
+
+/// Benchmark status stored in the `benchmarks` table.
+const COMPLETED: &str = "completed";
+const FAILED: &str = "failed";
+
+impl ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            Value::Null => SqlValue::Null,
+            Value::Bool(b) => SqlValue::Integer(*b as i64),
+            Value::Int(i) => SqlValue::Integer(*i),
+            Value::Float(f) => SqlValue::Real(*f),
+            Value::Str(s) => SqlValue::Text(s.clone()),
+        };
+        Ok(ToSqlOutput::Owned(value))
+    }
+}
+
+/// SQLite database accumulating the results of many Driver runs.
+///
+/// Measurements and monitor samples are stored in long format (one row per
+/// field), so benchmarks with different Measurable types share the same
+/// tables. Each row is keyed by its benchmark, and each benchmark by its run.
+pub struct SqliteDatabase {
+    connection: Connection,
+}
+
+impl SqliteDatabase {
+    /// Open (or create) the database at @param path.
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        trace!("opening results database {path:?}");
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        migrate(&connection)?;
+        Ok(SqliteDatabase { connection })
+    }
+
+    /// The underlying connection, for custom queries across runs.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Append a run and all of its benchmarks, returning the id of the run.
+    pub fn insert_run<T>(&mut self, bundle: &DriverBundle<T>) -> Result<i64>
+    where
+        T: Measurable,
+    {
+        let environment = &bundle.environment;
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "INSERT INTO runs
+                (timestamp, hostname, os, family, arch, cpus, version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                environment.timestamp as i64,
+                environment.hostname,
+                environment.os,
+                environment.family,
+                environment.arch,
+                environment.cpus as i64,
+                environment.version,
+            ],
+        )?;
+        let run_id = tx.last_insert_rowid();
+
+        for summary in &bundle.benchmark_summaries() {
+            let bundle = bundle.benchmark_bundles.get(&summary.benchmark);
+            let monitors = bundle.map(|bundle| {
+                let monitors = &bundle.monitor_bundle.monitor_measurements;
+                let mut names = monitors.keys().cloned().collect::<Vec<_>>();
+                names.sort();
+                names.join(",")
+            });
+            let benchmark_id =
+                insert_benchmark(&tx, run_id, summary, monitors)?;
+            let bundle = match bundle {
+                Some(bundle) => bundle,
+                None => continue,
+            };
+            insert_rows(
+                &tx,
+                "INSERT INTO measurements (benchmark_id, row, metric, value)
+                 VALUES (?1, ?2, ?3, ?4)",
+                benchmark_id,
+                None,
+                &bundle.measurements,
+            )?;
+            let monitors = &bundle.monitor_bundle.monitor_measurements;
+            for (monitor, measurements) in monitors {
+                insert_rows(
+                    &tx,
+                    "INSERT INTO monitor_samples
+                        (benchmark_id, monitor, row, metric, value)
+                     VALUES (?1, ?5, ?2, ?3, ?4)",
+                    benchmark_id,
+                    Some(monitor),
+                    measurements,
+                )?;
+            }
+        }
+
+        tx.commit()?;
+        debug!("stored run {run_id} in results database");
+        Ok(run_id)
+    }
+
+    /// Every value of @param metric recorded by @param benchmark, across all
+    /// runs, as a table of `run_id`, `timestamp`, `row` and `value`.
+    pub fn metric_history(
+        &self,
+        benchmark: &str,
+        metric: &str,
+    ) -> Result<Table> {
+        let mut statement = self.connection.prepare(
+            "SELECT runs.id, runs.timestamp, measurements.row,
+                    measurements.value
+             FROM measurements
+             JOIN benchmarks ON benchmarks.id = measurements.benchmark_id
+             JOIN runs ON runs.id = benchmarks.run_id
+             WHERE benchmarks.name = ?1 AND measurements.metric = ?2
+             ORDER BY runs.id, measurements.row",
+        )?;
+        let mut rows = statement.query(params![benchmark, metric])?;
+        let mut table = Table::new();
+        while let Some(row) = rows.next()? {
+            let value = match row.get::<_, SqlValue>(3)? {
+                SqlValue::Integer(i) => Value::Int(i),
+                SqlValue::Real(f) => Value::Float(f),
+                SqlValue::Text(s) => Value::Str(s),
+                SqlValue::Null | SqlValue::Blob(_) => Value::Null,
+            };
+            table.push(
+                &[
+                    ("run_id", Value::Int(row.get(0)?)),
+                    ("timestamp", Value::Int(row.get(1)?)),
+                    ("row", Value::Int(row.get(2)?)),
+                    ("value", value),
+                ]
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value))
+                .collect(),
+            );
+        }
+        Ok(table)
+    }
+}
+
+/// Adds the columns missing from a database created by an older version.
+fn migrate(connection: &Connection) -> Result<()> {
+    for (column, column_type) in ADDED_BENCHMARK_COLUMNS {
+        let exists: bool = connection.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('benchmarks')
+             WHERE name = ?1",
+            params![column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            debug!("adding column '{column}' to the benchmarks table");
+            connection.execute_batch(&format!(
+                "ALTER TABLE benchmarks ADD COLUMN {column} {column_type}"
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Inserts the benchmark of @param summary, with the comma-separated names
+/// of @param monitors unless it failed.
+fn insert_benchmark(
+    tx: &Transaction,
+    run_id: i64,
+    summary: &BenchmarkSummary,
+    monitors: Option<String>,
+) -> Result<i64> {
+    let status = match summary.completed {
+        true => COMPLETED,
+        false => FAILED,
+    };
+    tx.execute(
+        "INSERT INTO benchmarks
+            (run_id, name, status, duration_ns, error, monitors)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            run_id,
+            summary.benchmark,
+            status,
+            summary.duration_ns as i64,
+            summary.error,
+            monitors
+        ],
+    )?;
+    Ok(tx.last_insert_rowid())
+}
+
+/// Insert every field of every Measurable with @param sql, which binds the
+/// benchmark id, row index, metric name, value and optionally the monitor.
+fn insert_rows<T>(
+    tx: &Transaction,
+    sql: &str,
+    benchmark_id: i64,
+    monitor: Option<&String>,
+    measurements: &Measurements<T>,
+) -> Result<()>
+where
+    T: Measurable,
+{
+    let table = Table::from_measurements(measurements)?;
+    let mut statement = tx.prepare_cached(sql)?;
+    for (row, record) in table.rows().enumerate() {
+        for (metric, value) in record.iter() {
+            match monitor {
+                Some(monitor) => statement.execute(params![
+                    benchmark_id,
+                    row as i64,
+                    metric,
+                    value,
+                    monitor
+                ])?,
+                None => statement.execute(params![
+                    benchmark_id,
+                    row as i64,
+                    metric,
+                    value
+                ])?,
+            };
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::models::{
+        AccuracyBundle, BenchmarkBundle, Environment, Measurement,
+        MonitorBundle,
+    };
+    use crate::util::testing::TempDir;
+
+    #[derive(Serialize, Debug)]
+    struct Sample {
+        time: u32,
+    }
+
+    fn bundle() -> DriverBundle<Sample> {
+        let monitor_measurements = ["gpu.0", "cpu"]
+            .into_iter()
+            .map(|name| {
+                let sample = Measurement::from(Sample { time: 1 });
+                (name.to_owned(), [sample].into_iter().collect())
+            })
+            .collect();
+        let completed = BenchmarkBundle {
+            measurements: [Sample { time: 2 }].into_iter().collect(),
+            annotations: Measurements::new(),
+            monitor_bundle: MonitorBundle {
+                monitor_measurements,
+            },
+            accuracy_bundle: AccuracyBundle::default(),
+            duration: Duration::from_nanos(42),
+        };
+        DriverBundle {
+            benchmark_bundles: HashMap::from([("a".to_owned(), completed)]),
+            benchmark_failures: HashMap::from([(
+                "b".to_owned(),
+                "boom".to_owned(),
+            )]),
+            environment: Environment::collect(),
+        }
+    }
+
+    fn benchmarks(database: &SqliteDatabase) -> Vec<(String, Option<String>)> {
+        let mut statement = database
+            .connection()
+            .prepare("SELECT name, monitors FROM benchmarks ORDER BY name")
+            .unwrap();
+        statement
+            .query_map(params![], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn insert_run_stores_benchmark_monitors() -> Result<()> {
+        let dir = TempDir::new("sqlite-monitors");
+        let path = dir.join("runs.db");
+        let mut database = SqliteDatabase::open(&path)?;
+        database.insert_run(&bundle())?;
+        let benchmarks = benchmarks(&database);
+
+        assert_eq!(
+            benchmarks,
+            [
+                ("a".to_owned(), Some("cpu,gpu.0".to_owned())),
+                ("b".to_owned(), None)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn open_adds_columns_missing_from_older_databases() -> Result<()> {
+        let dir = TempDir::new("sqlite-migrate");
+        let path = dir.join("runs.db");
+        Connection::open(&path)?.execute_batch(
+            "CREATE TABLE benchmarks (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id      INTEGER NOT NULL,
+                name        TEXT NOT NULL,
+                status      TEXT NOT NULL,
+                error       TEXT,
+                UNIQUE (run_id, name)
+            );",
+        )?;
+        let mut database = SqliteDatabase::open(&path)?;
+        database.insert_run(&bundle())?;
+        let benchmarks = benchmarks(&database);
+        let duration_ns: i64 = database.connection().query_row(
+            "SELECT duration_ns FROM benchmarks WHERE name = 'a'",
+            params![],
+            |row| row.get(0),
+        )?;
+
+        assert_eq!(benchmarks.len(), 2);
+        assert_eq!(duration_ns, 42);
+        Ok(())
+    }
+}